@@ -1,11 +1,26 @@
-use crate::error::AppError;
+use crate::crash_loop::{self, CrashLoopInputs};
+use crate::dry_run::{self, DryRunResult};
+use crate::error::{AppError, LaunchctlFailure};
+use crate::exit_code;
+use crate::history::{self, HistoryRange, RunEvent};
 use crate::launchctl;
+use crate::logs::{self, LogStats, ParsedLogLine, TimelineLine};
+use crate::monitor;
+use crate::newsyslog::{self, LogRotationStatus, RotationOptions};
 use crate::plist_util;
 use crate::types::PlistConfig;
 use crate::types::{JobListEntry, JobSource, JobStatus, LaunchdJob};
+use crate::unified_log::{self, UnifiedLogEntry};
 use std::collections::HashMap;
+use std::time::Duration;
+
+/// Last start time in epoch milliseconds. Uses the start recorded in the run history when
+/// there is one, otherwise falls back to the newest log file modification time.
+fn get_last_run_at(config: &PlistConfig, history_started_at: Option<i64>) -> Option<String> {
+    if let Some(at) = history_started_at {
+        return Some(at.to_string());
+    }
 
-fn get_last_run_at(config: &PlistConfig) -> Option<String> {
     let paths = [&config.standard_out_path, &config.standard_error_path];
     let mut latest: Option<u64> = None;
 
@@ -80,14 +95,46 @@ fn is_home_agent(source: &JobSource, config: &PlistConfig) -> bool {
     strings.iter().any(|s| references_home_path(s, &home))
 }
 
+/// Explanation when a loaded job is crash-looping. `launchctl print` (and, when
+/// `check_unified_log` is set, launchd's unified-log messages) are only consulted for jobs
+/// whose recorded history already shows repeated failures, to keep the job list fast.
+fn crash_loop_reason(label: &str, events: &[RunEvent], check_unified_log: bool) -> Option<String> {
+    let now = crate::storage::current_millis();
+    if !crash_loop::is_candidate(events, now) {
+        return None;
+    }
+    let details = launchctl::print(label).ok();
+    let throttle_messages = if check_unified_log {
+        unified_log::show(label, None, now - crash_loop::WINDOW_MS, None)
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter(|e| e.kind == Some(unified_log::LaunchdEventKind::Throttled))
+                    .count()
+            })
+            .unwrap_or(0)
+    } else {
+        0
+    };
+    crash_loop::assess(&CrashLoopInputs {
+        events,
+        details: details.as_ref(),
+        throttle_messages,
+        now,
+    })
+}
+
 fn ensure_user_agent(plist_path: &str) -> Result<(), AppError> {
     let home = dirs::home_dir().unwrap_or_default();
     let user_agents = home.join("Library/LaunchAgents");
     if !plist_path.starts_with(user_agents.to_str().unwrap_or("")) {
-        return Err(AppError::Launchctl(
-            "Cannot start/stop system agents or daemons. Only user agents (~/Library/LaunchAgents) can be managed."
+        return Err(AppError::PermissionDenied(LaunchctlFailure {
+            operation: "manage".to_string(),
+            target: plist_path.to_string(),
+            stderr: String::new(),
+            hint: "Cannot start/stop system agents or daemons. Only user agents (~/Library/LaunchAgents) can be managed."
                 .to_string(),
-        ));
+        }));
     }
     Ok(())
 }
@@ -95,7 +142,23 @@ fn ensure_user_agent(plist_path: &str) -> Result<(), AppError> {
 #[tauri::command]
 pub async fn list_jobs() -> Result<Vec<JobListEntry>, AppError> {
     let plist_files = plist_util::scan_plist_files();
-    let loaded = launchctl::list_loaded().unwrap_or_default();
+    let loaded = match launchctl::list_loaded() {
+        Ok(loaded) => {
+            // History is best-effort; a failed write must not hide the job list.
+            let _ = history::record(&loaded);
+            loaded
+        }
+        Err(_) => Vec::new(),
+    };
+    let events = history::load_events();
+    let started_at = history::last_started_at(&events);
+    let mut events_by_label: HashMap<&str, Vec<RunEvent>> = HashMap::new();
+    for e in &events {
+        events_by_label
+            .entry(e.label.as_str())
+            .or_default()
+            .push(e.clone());
+    }
 
     let loaded_map: HashMap<String, &launchctl::LoadedService> =
         loaded.iter().map(|s| (s.label.clone(), s)).collect();
@@ -117,16 +180,29 @@ pub async fn list_jobs() -> Result<Vec<JobListEntry>, AppError> {
         } else {
             (JobStatus::Unloaded, None, None)
         };
+        let status_reason = match status {
+            JobStatus::Unloaded => None,
+            _ => events_by_label
+                .get(config.label.as_str())
+                .and_then(|events| crash_loop_reason(&config.label, events, false)),
+        };
+        let status = if status_reason.is_some() {
+            JobStatus::CrashLooping
+        } else {
+            status
+        };
 
-        let last_run_at = get_last_run_at(&config);
+        let last_run_at = get_last_run_at(&config, started_at.get(&config.label).copied());
         let home_agent = is_home_agent(&source, &config);
         entries.push(JobListEntry {
             label: config.label,
             pid,
             last_exit_code: exit_code,
+            last_exit_info: exit_code.map(exit_code::decode),
             plist_path: path,
             source,
             status,
+            status_reason,
             last_run_at,
             is_home_agent: home_agent,
         });
@@ -166,19 +242,59 @@ pub async fn get_job_detail(plist_path: String) -> Result<LaunchdJob, AppError>
         crate::types::JobSource::UserAgent
     };
 
-    let last_run_at = get_last_run_at(&plist);
+    let events: Vec<RunEvent> = history::load_events()
+        .into_iter()
+        .filter(|e| e.label == plist.label)
+        .collect();
+    let status_reason = match status {
+        JobStatus::Unloaded => None,
+        _ => crash_loop_reason(&plist.label, &events, true),
+    };
+    let status = if status_reason.is_some() {
+        JobStatus::CrashLooping
+    } else {
+        status
+    };
+
+    let last_run_at = get_last_run_at(
+        &plist,
+        history::last_started_at(&events).get(&plist.label).copied(),
+    );
     Ok(LaunchdJob {
         label: plist.label.clone(),
         plist_path,
         source,
         status,
+        status_reason,
         pid,
         last_exit_code: exit_code,
+        last_exit_info: exit_code.map(exit_code::decode),
         plist,
         last_run_at,
     })
 }
 
+/// Recorded start/exit events for a job, oldest first. `range` bounds are epoch milliseconds.
+#[tauri::command]
+pub async fn get_job_history(
+    label: String,
+    range: Option<HistoryRange>,
+) -> Result<Vec<RunEvent>, AppError> {
+    history::job_history(&label, &range.unwrap_or_default())
+}
+
+/// Sets how often the background monitor samples `launchctl list`. 0 pauses it.
+#[tauri::command]
+pub async fn set_monitor_interval(seconds: u64) -> Result<(), AppError> {
+    monitor::set_interval(seconds);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_monitor_interval() -> Result<u64, AppError> {
+    Ok(monitor::interval())
+}
+
 #[tauri::command]
 pub async fn start_job(plist_path: String) -> Result<(), AppError> {
     ensure_user_agent(&plist_path)?;
@@ -268,10 +384,26 @@ pub async fn delete_job(plist_path: String, label: String) -> Result<(), AppErro
     Ok(())
 }
 
+/// Runs the job once in the foreground as launchd would, without installing it.
+#[tauri::command]
+pub async fn dry_run_job(
+    config: PlistConfig,
+    timeout_ms: Option<u64>,
+) -> Result<DryRunResult, AppError> {
+    dry_run::run(
+        &config,
+        Duration::from_millis(timeout_ms.unwrap_or(dry_run::DEFAULT_TIMEOUT_MS)),
+    )
+}
+
 #[derive(serde::Serialize)]
 pub struct LogFileResult {
     content: String,
     modified_at: Option<String>,
+    /// Structured view of the returned (tailed) lines.
+    lines: Vec<ParsedLogLine>,
+    /// Format and error/warning counts for the whole file, not just the tail.
+    stats: LogStats,
 }
 
 #[tauri::command]
@@ -301,6 +433,11 @@ pub async fn read_log_file(
         });
 
     let content = std::fs::read_to_string(&path)?;
+    let reference = metadata
+        .modified()
+        .map(chrono::DateTime::<chrono::Local>::from)
+        .unwrap_or_else(|_| chrono::Local::now());
+    let stats = logs::compute_stats(&content, reference);
     let content = match tail_lines {
         Some(n) => {
             let lines: Vec<&str> = content.lines().collect();
@@ -309,13 +446,96 @@ pub async fn read_log_file(
         }
         None => content,
     };
+    let lines = content
+        .lines()
+        .map(|l| logs::parse_line(l, stats.format, reference))
+        .collect();
 
     Ok(LogFileResult {
         content,
         modified_at,
+        lines,
+        stats,
     })
 }
 
+/// Error/warning counts per log file, for badges in the job list. Missing or unreadable
+/// files are left out of the result.
+#[tauri::command]
+pub async fn get_log_stats(paths: Vec<String>) -> Result<HashMap<String, LogStats>, AppError> {
+    let mut result = HashMap::new();
+    for path in paths {
+        let Ok(metadata) = std::fs::metadata(&path) else {
+            continue;
+        };
+        let Ok(bytes) = std::fs::read(&path) else {
+            continue;
+        };
+        let reference = metadata
+            .modified()
+            .map(chrono::DateTime::<chrono::Local>::from)
+            .unwrap_or_else(|_| chrono::Local::now());
+        let stats = logs::compute_stats(&String::from_utf8_lossy(&bytes), reference);
+        result.insert(path, stats);
+    }
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn read_merged_logs(
+    stdout_path: Option<String>,
+    stderr_path: Option<String>,
+    tail_lines: Option<usize>,
+) -> Result<Vec<TimelineLine>, AppError> {
+    // A shared stdout/stderr file is read once, as stdout.
+    let stderr_path = stderr_path.filter(|p| Some(p) != stdout_path.as_ref());
+    let existing: Vec<&String> = [&stdout_path, &stderr_path]
+        .into_iter()
+        .flatten()
+        .filter(|p| std::path::Path::new(p).exists())
+        .collect();
+    if existing.is_empty() {
+        return Err(AppError::NotFound(
+            "neither stdout nor stderr log file exists".to_string(),
+        ));
+    }
+
+    // Syslog-style timestamps have no year; resolve them against the newest file's mtime.
+    let reference = existing
+        .iter()
+        .filter_map(|p| std::fs::metadata(p).and_then(|m| m.modified()).ok())
+        .max()
+        .map(chrono::DateTime::<chrono::Local>::from)
+        .unwrap_or_else(chrono::Local::now);
+
+    let read = |path: &Option<String>| -> Result<String, AppError> {
+        match path {
+            Some(p) if std::path::Path::new(p).exists() => {
+                let bytes = std::fs::read(p)?;
+                Ok(String::from_utf8_lossy(&bytes).into_owned())
+            }
+            _ => Ok(String::new()),
+        }
+    };
+    let mut merged = logs::merge_streams(&read(&stdout_path)?, &read(&stderr_path)?, reference);
+    if let Some(n) = tail_lines {
+        merged = merged.split_off(merged.len().saturating_sub(n));
+    }
+    Ok(merged)
+}
+
+/// launchd's unified-log messages about the job (and the job's own messages when `pid` is
+/// given) between `start` and `end`, both in epoch milliseconds.
+#[tauri::command]
+pub async fn get_unified_log(
+    label: String,
+    pid: Option<u32>,
+    start: i64,
+    end: Option<i64>,
+) -> Result<Vec<UnifiedLogEntry>, AppError> {
+    unified_log::show(&label, pid, start, end)
+}
+
 #[tauri::command]
 pub async fn clear_log_file(path: String) -> Result<(), AppError> {
     if !std::path::Path::new(&path).exists() {
@@ -334,11 +554,38 @@ pub async fn open_log_in_editor(path: String) -> Result<(), AppError> {
     Ok(())
 }
 
+#[tauri::command]
+pub async fn preview_log_rotation(
+    plist_path: String,
+    options: RotationOptions,
+) -> Result<String, AppError> {
+    let config = plist_util::parse_plist(&plist_path)?;
+    newsyslog::generate_config(&config, &options)
+}
+
+#[tauri::command]
+pub async fn write_log_rotation(
+    plist_path: String,
+    options: RotationOptions,
+) -> Result<String, AppError> {
+    let config = plist_util::parse_plist(&plist_path)?;
+    let content = newsyslog::generate_config(&config, &options)?;
+    newsyslog::write_config(&config.label, &content)
+}
+
+#[tauri::command]
+pub async fn get_log_rotation_status(
+    plist_path: String,
+) -> Result<Vec<LogRotationStatus>, AppError> {
+    let config = plist_util::parse_plist(&plist_path)?;
+    Ok(newsyslog::coverage(&config, &newsyslog::scan_existing()))
+}
+
 #[tauri::command]
 pub async fn get_home_dir() -> Result<String, AppError> {
     dirs::home_dir()
         .and_then(|p| p.to_str().map(String::from))
-        .ok_or_else(|| AppError::Launchctl("could not determine home directory".to_string()))
+        .ok_or_else(|| AppError::NotFound("home directory".to_string()))
 }
 
 #[tauri::command]
@@ -0,0 +1,158 @@
+use crate::exit_code;
+use crate::history::{RunEvent, RunEventKind};
+use crate::launchctl::ServiceDetails;
+
+/// A job is crash-looping once it has exited abnormally this many times...
+pub const RESTART_THRESHOLD: usize = 5;
+/// ...within this window.
+pub const WINDOW_MS: i64 = 10 * 60 * 1000;
+
+/// Everything known about a job's recent runs. Only `events` is required; the other
+/// sources are gathered when they are cheap enough to fetch.
+pub struct CrashLoopInputs<'a> {
+    /// The job's recorded run history (any order, any labels are ignored by the caller).
+    pub events: &'a [RunEvent],
+    /// Parsed `launchctl print` output, if it was fetched.
+    pub details: Option<&'a ServiceDetails>,
+    /// Number of "respawn throttled" messages launchd logged for the job in the window.
+    pub throttle_messages: usize,
+    pub now: i64,
+}
+
+fn abnormal_exits_in_window(events: &[RunEvent], now: i64) -> Vec<&RunEvent> {
+    events
+        .iter()
+        .filter(|e| e.kind == RunEventKind::Exited)
+        .filter(|e| e.exit_code.is_some_and(|c| c != 0))
+        .filter(|e| now - e.at <= WINDOW_MS)
+        .collect()
+}
+
+/// True when the history alone has enough abnormal exits in the window to be worth a closer
+/// look with `launchctl print` (at least two, so a single failure never triggers it).
+pub fn is_candidate(events: &[RunEvent], now: i64) -> bool {
+    abnormal_exits_in_window(events, now).len() >= 2
+}
+
+/// Decides whether a job is crash-looping and, if so, explains why in one sentence.
+///
+/// A job is flagged when it exited abnormally at least [`RESTART_THRESHOLD`] times within
+/// [`WINDOW_MS`], or when it keeps failing and launchd has started throttling its respawns.
+pub fn assess(inputs: &CrashLoopInputs) -> Option<String> {
+    let exits = abnormal_exits_in_window(inputs.events, inputs.now);
+    let window_min = WINDOW_MS / 60_000;
+    let last_code = exits.iter().max_by_key(|e| e.at).and_then(|e| e.exit_code);
+    let last_reason = inputs
+        .details
+        .and_then(|d| {
+            d.last_terminating_signal
+                .clone()
+                .or_else(|| d.last_exit_code.clone())
+        })
+        .or_else(|| {
+            last_code.map(|c| match exit_code::decode(c).name {
+                Some(name) => format!("{c} {name}"),
+                None => c.to_string(),
+            })
+        });
+    let reason_suffix = last_reason
+        .map(|r| format!(" (last exit: {r})"))
+        .unwrap_or_default();
+    let runs_suffix = inputs
+        .details
+        .and_then(|d| d.runs)
+        .map(|r| format!(", {r} runs since load"))
+        .unwrap_or_default();
+
+    if exits.len() >= RESTART_THRESHOLD {
+        return Some(format!(
+            "Exited abnormally {} times in the last {window_min} minutes{reason_suffix}{runs_suffix}.",
+            exits.len()
+        ));
+    }
+
+    let throttled =
+        inputs.details.is_some_and(|d| d.is_throttled()) || inputs.throttle_messages > 0;
+    if throttled && !exits.is_empty() {
+        return Some(format!(
+            "launchd is throttling respawns after {} abnormal exit{} in the last {window_min} minutes{reason_suffix}{runs_suffix}.",
+            exits.len(),
+            if exits.len() == 1 { "" } else { "s" }
+        ));
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 100 * 60 * 1000;
+
+    fn exit(at: i64, code: i32) -> RunEvent {
+        RunEvent {
+            label: "a".to_string(),
+            kind: RunEventKind::Exited,
+            pid: 1,
+            exit_code: Some(code),
+            at,
+        }
+    }
+
+    fn inputs<'a>(
+        events: &'a [RunEvent],
+        details: Option<&'a ServiceDetails>,
+    ) -> CrashLoopInputs<'a> {
+        CrashLoopInputs {
+            events,
+            details,
+            throttle_messages: 0,
+            now: NOW,
+        }
+    }
+
+    #[test]
+    fn test_flags_repeated_abnormal_exits() {
+        let events: Vec<RunEvent> = (0..RESTART_THRESHOLD as i64)
+            .map(|i| exit(NOW - i * 60_000, 78))
+            .collect();
+        let reason = assess(&inputs(&events, None)).unwrap();
+        assert!(reason.starts_with("Exited abnormally 5 times"));
+        assert!(reason.contains("last exit: 78"));
+    }
+
+    #[test]
+    fn test_ignores_old_and_clean_exits() {
+        let mut events: Vec<RunEvent> = (0..10).map(|i| exit(NOW - i * 1000, 0)).collect();
+        events.extend((0..10).map(|i| exit(NOW - WINDOW_MS - 1 - i, 1)));
+        assert_eq!(assess(&inputs(&events, None)), None);
+        assert!(!is_candidate(&events, NOW));
+    }
+
+    #[test]
+    fn test_throttled_state_with_failures() {
+        let events = vec![exit(NOW - 1000, 1), exit(NOW - 20_000, 1)];
+        let details = ServiceDetails {
+            state: Some("spawn scheduled".to_string()),
+            runs: Some(42),
+            last_exit_code: Some("1".to_string()),
+            ..Default::default()
+        };
+        let reason = assess(&inputs(&events, Some(&details))).unwrap();
+        assert!(reason.starts_with("launchd is throttling respawns after 2 abnormal exits"));
+        assert!(reason.contains("42 runs since load"));
+        assert!(is_candidate(&events, NOW));
+
+        // Throttled but never failed: nothing to report.
+        assert_eq!(assess(&inputs(&[], Some(&details))), None);
+    }
+
+    #[test]
+    fn test_throttle_messages_count_as_throttling() {
+        let events = vec![exit(NOW - 1000, -9)];
+        let mut i = inputs(&events, None);
+        assert_eq!(assess(&i), None);
+        i.throttle_messages = 1;
+        assert!(assess(&i).unwrap().contains("1 abnormal exit in"));
+    }
+}
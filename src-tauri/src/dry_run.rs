@@ -0,0 +1,280 @@
+use crate::error::AppError;
+use crate::exit_code;
+use crate::types::{ExitCodeInfo, PlistConfig};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::os::unix::process::{CommandExt, ExitStatusExt};
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_TIMEOUT_MS: u64 = 30_000;
+/// Captured output beyond this is discarded (the pipe is still drained).
+const MAX_OUTPUT_BYTES: u64 = 1024 * 1024;
+/// launchd's default PATH for jobs that do not set one.
+const DEFAULT_PATH: &str = "/usr/bin:/bin:/usr/sbin:/sbin";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DryRunResult {
+    /// The argv that was executed.
+    pub argv: Vec<String>,
+    pub working_directory: String,
+    /// launchd-style status: the exit code, or the negated signal number if killed.
+    pub exit_code: Option<i32>,
+    pub exit_info: Option<ExitCodeInfo>,
+    pub timed_out: bool,
+    pub stdout: String,
+    pub stderr: String,
+    pub duration_ms: u64,
+}
+
+/// Keys the dry run honours that are not part of [`PlistConfig`], read from its raw XML.
+#[derive(Debug, Default, PartialEq)]
+struct ExtraKeys {
+    standard_in_path: Option<String>,
+    umask: Option<u32>,
+}
+
+fn extra_keys(raw_xml: &str) -> ExtraKeys {
+    let Ok(value) = plist::Value::from_reader_xml(raw_xml.as_bytes()) else {
+        return ExtraKeys::default();
+    };
+    let Some(dict) = value.as_dictionary() else {
+        return ExtraKeys::default();
+    };
+    let umask = match dict.get("Umask") {
+        Some(plist::Value::Integer(i)) => i.as_unsigned().map(|u| u as u32),
+        // Newer launchd versions also accept an octal string such as "022".
+        Some(plist::Value::String(s)) => u32::from_str_radix(s, 8).ok(),
+        _ => None,
+    };
+    ExtraKeys {
+        standard_in_path: dict
+            .get("StandardInPath")
+            .and_then(|v| v.as_string())
+            .map(String::from),
+        umask,
+    }
+}
+
+/// The argv launchd would execute: `ProgramArguments`, with `Program` (when set) replacing
+/// the path that is executed.
+fn argv(config: &PlistConfig) -> Result<(String, Vec<String>), AppError> {
+    let args = config.program_arguments.clone().unwrap_or_default();
+    let program = config
+        .program
+        .clone()
+        .or_else(|| args.first().cloned())
+        .ok_or_else(|| AppError::Plist("neither Program nor ProgramArguments is set".to_string()))?;
+    let args = if args.is_empty() {
+        vec![program.clone()]
+    } else {
+        args
+    };
+    Ok((program, args))
+}
+
+/// launchd's minimal per-user environment, overlaid with the job's `EnvironmentVariables`.
+fn environment(config: &PlistConfig) -> HashMap<String, String> {
+    let mut env = HashMap::new();
+    env.insert("PATH".to_string(), DEFAULT_PATH.to_string());
+    if let Some(home) = dirs::home_dir() {
+        env.insert("HOME".to_string(), home.to_string_lossy().into_owned());
+    }
+    for key in ["USER", "LOGNAME", "SHELL", "TMPDIR"] {
+        if let Ok(value) = std::env::var(key) {
+            env.insert(key.to_string(), value);
+        }
+    }
+    if let Some(vars) = &config.environment_variables {
+        env.extend(vars.clone());
+    }
+    env
+}
+
+fn read_capped(mut pipe: impl Read + Send + 'static) -> std::thread::JoinHandle<String> {
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = pipe.by_ref().take(MAX_OUTPUT_BYTES).read_to_end(&mut buf);
+        let _ = std::io::copy(&mut pipe, &mut std::io::sink());
+        String::from_utf8_lossy(&buf).into_owned()
+    })
+}
+
+fn wait_with_timeout(child: &mut Child, timeout: Duration) -> std::io::Result<(std::process::ExitStatus, bool)> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok((status, false));
+        }
+        if Instant::now() >= deadline {
+            // Kill the whole process group so children holding the pipes open die too,
+            // as launchd does when a job exits.
+            let _ = Command::new("kill")
+                .args(["-KILL", &format!("-{}", child.id())])
+                .status();
+            let _ = child.kill();
+            return Ok((child.wait()?, true));
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+/// Runs the job once in the foreground the way launchd would start it: from a clean
+/// environment, in `WorkingDirectory` (default `/`), with `StandardInPath` as stdin and
+/// `Umask` applied. stdout and stderr are captured rather than written to the job's log files.
+pub fn run(config: &PlistConfig, timeout: Duration) -> Result<DryRunResult, AppError> {
+    let (program, args) = argv(config)?;
+    let extra = extra_keys(&config.raw_xml);
+    let working_directory = config
+        .working_directory
+        .clone()
+        .unwrap_or_else(|| "/".to_string());
+
+    // Applying a umask needs a hook between fork and exec; a tiny `sh` wrapper does that
+    // without unsafe code and then `exec`s the program so nothing else changes.
+    let mut command = match extra.umask {
+        Some(mask) => {
+            let mut c = Command::new("/bin/sh");
+            c.arg("-c")
+                .arg(format!("umask {mask:03o} && exec \"$0\" \"$@\""))
+                .arg(&program)
+                .args(&args[1..]);
+            c
+        }
+        None => {
+            let mut c = Command::new(&program);
+            c.args(&args[1..]);
+            c
+        }
+    };
+    command
+        .process_group(0)
+        .env_clear()
+        .envs(environment(config))
+        .current_dir(&working_directory)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    command.stdin(match &extra.standard_in_path {
+        Some(path) => Stdio::from(
+            File::open(path)
+                .map_err(|e| AppError::Io(std::io::Error::new(e.kind(), format!("{path}: {e}"))))?,
+        ),
+        None => Stdio::null(),
+    });
+
+    let started = Instant::now();
+    let mut child = command
+        .spawn()
+        .map_err(|e| AppError::Io(std::io::Error::new(e.kind(), format!("{program}: {e}"))))?;
+    let stdout = child.stdout.take().map(read_capped);
+    let stderr = child.stderr.take().map(read_capped);
+    let (status, timed_out) = wait_with_timeout(&mut child, timeout)?;
+    let duration_ms = started.elapsed().as_millis() as u64;
+
+    let collect = |h: Option<std::thread::JoinHandle<String>>| {
+        h.and_then(|h| h.join().ok()).unwrap_or_default()
+    };
+    let exit_code = status.code().or_else(|| status.signal().map(|s| -s));
+    Ok(DryRunResult {
+        argv: args,
+        working_directory,
+        exit_code,
+        exit_info: exit_code.map(exit_code::decode),
+        timed_out,
+        stdout: collect(stdout),
+        stderr: collect(stderr),
+        duration_ms,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shell_config(script: &str) -> PlistConfig {
+        PlistConfig {
+            label: "com.example.dry".to_string(),
+            program: None,
+            program_arguments: Some(vec![
+                "/bin/sh".to_string(),
+                "-c".to_string(),
+                script.to_string(),
+            ]),
+            run_at_load: None,
+            keep_alive: None,
+            start_interval: None,
+            start_calendar_interval: None,
+            standard_out_path: None,
+            standard_error_path: None,
+            working_directory: None,
+            environment_variables: None,
+            disabled: None,
+            wake_system: None,
+            raw_xml: String::new(),
+        }
+    }
+
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    #[test]
+    fn test_captures_output_and_status() {
+        let result = run(&shell_config("echo out; echo err >&2; exit 78"), TIMEOUT).unwrap();
+        assert_eq!(result.stdout, "out\n");
+        assert_eq!(result.stderr, "err\n");
+        assert_eq!(result.exit_code, Some(78));
+        assert_eq!(
+            result.exit_info.unwrap().name.as_deref(),
+            Some("EX_CONFIG")
+        );
+        assert!(!result.timed_out);
+    }
+
+    #[test]
+    fn test_clean_environment_and_working_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = shell_config("pwd; echo \"$PATH|$FOO|${CARGO:-unset}\"");
+        config.working_directory = Some(dir.path().to_string_lossy().into_owned());
+        config.environment_variables =
+            Some(HashMap::from([("FOO".to_string(), "bar".to_string())]));
+        let result = run(&config, TIMEOUT).unwrap();
+        let lines: Vec<&str> = result.stdout.lines().collect();
+        assert_eq!(
+            std::fs::canonicalize(lines[0]).unwrap(),
+            std::fs::canonicalize(dir.path()).unwrap()
+        );
+        assert_eq!(lines[1], format!("{DEFAULT_PATH}|bar|unset"));
+    }
+
+    #[test]
+    fn test_stdin_path_and_umask() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("in.txt");
+        std::fs::write(&input, "hello\n").unwrap();
+        let mut config = shell_config("cat; umask");
+        config.raw_xml = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<plist version=\"1.0\"><dict>\
+             <key>StandardInPath</key><string>{}</string>\
+             <key>Umask</key><integer>63</integer></dict></plist>",
+            input.display()
+        );
+        let result = run(&config, TIMEOUT).unwrap();
+        assert_eq!(result.stdout, "hello\n0077\n");
+    }
+
+    #[test]
+    fn test_timeout_kills_process() {
+        let result = run(&shell_config("sleep 5"), Duration::from_millis(100)).unwrap();
+        assert!(result.timed_out);
+        assert_eq!(result.exit_code, Some(-9));
+        assert!(result.duration_ms < 5000);
+    }
+
+    #[test]
+    fn test_missing_program_is_an_error() {
+        let mut config = shell_config("");
+        config.program_arguments = Some(vec!["/nonexistent/binary".to_string()]);
+        assert!(matches!(run(&config, TIMEOUT), Err(AppError::Io(_))));
+    }
+}
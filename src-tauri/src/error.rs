@@ -1,9 +1,55 @@
 use serde::Serialize;
 
+/// What was attempted when launchctl (or a launchd-related precondition) failed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LaunchctlFailure {
+    /// launchctl subcommand, e.g. `bootstrap`.
+    pub operation: String,
+    /// Domain/service target or plist path the operation was applied to.
+    pub target: String,
+    /// launchctl's stderr, verbatim.
+    pub stderr: String,
+    /// What the user can do about it.
+    pub hint: String,
+}
+
+impl std::fmt::Display for LaunchctlFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.operation, self.target)?;
+        let stderr = self.stderr.trim();
+        if !stderr.is_empty() {
+            write!(f, ": {stderr}")?;
+        }
+        if !self.hint.is_empty() {
+            write!(f, ". {}", self.hint)?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum AppError {
+    #[error("service already loaded: {0}")]
+    ServiceAlreadyLoaded(LaunchctlFailure),
+
+    #[error("service not found: {0}")]
+    ServiceNotFound(LaunchctlFailure),
+
+    #[error("service disabled: {0}")]
+    ServiceDisabled(LaunchctlFailure),
+
+    #[error("permission denied: {0}")]
+    PermissionDenied(LaunchctlFailure),
+
+    #[error("input/output error: {0}")]
+    InputOutputError(LaunchctlFailure),
+
+    #[error("invalid plist: {0}")]
+    InvalidPlist(LaunchctlFailure),
+
+    /// A launchctl failure that matched none of the known patterns.
     #[error("launchctl error: {0}")]
-    Launchctl(String),
+    Launchctl(LaunchctlFailure),
 
     #[error("plist error: {0}")]
     Plist(String),
@@ -15,11 +61,181 @@ pub enum AppError {
     NotFound(String),
 }
 
+impl AppError {
+    /// Classifies a failed launchctl invocation from its stderr.
+    pub fn from_launchctl(operation: &str, target: &str, stderr: &str) -> AppError {
+        let lower = stderr.to_lowercase();
+        let has = |patterns: &[&str]| patterns.iter().any(|p| lower.contains(p));
+        let failure = |hint: &str| LaunchctlFailure {
+            operation: operation.to_string(),
+            target: target.to_string(),
+            stderr: stderr.trim().to_string(),
+            hint: hint.to_string(),
+        };
+
+        if has(&[
+            "already loaded",
+            "already bootstrapped",
+            "operation already in progress",
+        ]) {
+            AppError::ServiceAlreadyLoaded(failure(
+                "The service is already loaded. Boot it out first to reload a changed plist.",
+            ))
+        } else if has(&[
+            "not loaded",
+            "no such process",
+            "could not find specified service",
+            "could not find service",
+        ]) {
+            AppError::ServiceNotFound(failure(
+                "The service is not loaded in this domain. Load it first, or check the label.",
+            ))
+        } else if has(&["service is disabled"]) {
+            AppError::ServiceDisabled(failure(
+                "The service is disabled. Enable it before loading or starting it.",
+            ))
+        } else if has(&[
+            "operation not permitted",
+            "permission denied",
+            "not privileged",
+            "bad ownership",
+        ]) {
+            AppError::PermissionDenied(failure(
+                "Check the plist's owner and mode (user agents: owned by you, not group/world \
+                 writable) and grant Full Disk Access if the file is in a protected location.",
+            ))
+        } else if has(&["invalid property list", "malformed", "invalid or missing"]) {
+            AppError::InvalidPlist(failure(
+                "Fix the plist: run `plutil -lint` on it and make sure Label and Program or \
+                 ProgramArguments are set.",
+            ))
+        } else if has(&["input/output error"]) {
+            AppError::InputOutputError(failure(
+                "launchd's generic bootstrap failure: check the plist with `plutil -lint`, its \
+                 ownership and permissions, and whether the service is already loaded. \
+                 Re-running the command as root prints richer errors.",
+            ))
+        } else {
+            AppError::Launchctl(failure(""))
+        }
+    }
+
+    /// Stable identifier the frontend can match on.
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::ServiceAlreadyLoaded(_) => "ServiceAlreadyLoaded",
+            AppError::ServiceNotFound(_) => "ServiceNotFound",
+            AppError::ServiceDisabled(_) => "ServiceDisabled",
+            AppError::PermissionDenied(_) => "PermissionDenied",
+            AppError::InputOutputError(_) => "InputOutputError",
+            AppError::InvalidPlist(_) => "InvalidPlist",
+            AppError::Launchctl(_) => "Launchctl",
+            AppError::Plist(_) => "Plist",
+            AppError::Io(_) => "Io",
+            AppError::NotFound(_) => "NotFound",
+        }
+    }
+
+    pub fn launchctl_failure(&self) -> Option<&LaunchctlFailure> {
+        match self {
+            AppError::ServiceAlreadyLoaded(f)
+            | AppError::ServiceNotFound(f)
+            | AppError::ServiceDisabled(f)
+            | AppError::PermissionDenied(f)
+            | AppError::InputOutputError(f)
+            | AppError::InvalidPlist(f)
+            | AppError::Launchctl(f) => Some(f),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ErrorPayload<'a> {
+    code: &'static str,
+    message: String,
+    #[serde(flatten)]
+    details: Option<&'a LaunchctlFailure>,
+}
+
 impl Serialize for AppError {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::ser::Serializer,
     {
-        serializer.serialize_str(self.to_string().as_ref())
+        ErrorPayload {
+            code: self.code(),
+            message: self.to_string(),
+            details: self.launchctl_failure(),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_launchctl_classification() {
+        let cases = [
+            (
+                "Bootstrap failed: 5: Input/output error",
+                "InputOutputError",
+            ),
+            (
+                "Bootstrap failed: 37: Operation already in progress",
+                "ServiceAlreadyLoaded",
+            ),
+            ("Boot-out failed: 3: No such process", "ServiceNotFound"),
+            ("Could not find specified service", "ServiceNotFound"),
+            (
+                "Bootstrap failed: 119: Service is disabled",
+                "ServiceDisabled",
+            ),
+            (
+                "Bootstrap failed: 1: Operation not permitted",
+                "PermissionDenied",
+            ),
+            ("Invalid property list", "InvalidPlist"),
+            ("something else", "Launchctl"),
+        ];
+        for (stderr, code) in cases {
+            assert_eq!(
+                AppError::from_launchctl("bootstrap", "gui/501", stderr).code(),
+                code,
+                "{stderr}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_serialize_launchctl_error() {
+        let err = AppError::from_launchctl(
+            "bootout",
+            "gui/501/com.example.a",
+            "Boot-out failed: 3: No such process\n",
+        );
+        let json = serde_json::to_value(&err).unwrap();
+        assert_eq!(json["code"], "ServiceNotFound");
+        assert_eq!(json["operation"], "bootout");
+        assert_eq!(json["target"], "gui/501/com.example.a");
+        assert_eq!(json["stderr"], "Boot-out failed: 3: No such process");
+        assert!(json["hint"].as_str().unwrap().contains("not loaded"));
+        assert!(
+            json["message"]
+                .as_str()
+                .unwrap()
+                .starts_with("service not found")
+        );
+    }
+
+    #[test]
+    fn test_serialize_plain_error() {
+        let json = serde_json::to_value(AppError::NotFound("/x.plist".to_string())).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"code": "NotFound", "message": "file not found: /x.plist"})
+        );
     }
 }
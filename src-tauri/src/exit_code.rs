@@ -0,0 +1,232 @@
+use crate::types::{ExitCodeCategory, ExitCodeInfo};
+
+/// macOS signal numbers (`<sys/signal.h>`), indexed by signal number - 1.
+const SIGNALS: [(&str, &str); 31] = [
+    ("SIGHUP", "hangup"),
+    ("SIGINT", "interrupted"),
+    ("SIGQUIT", "quit"),
+    ("SIGILL", "illegal instruction"),
+    ("SIGTRAP", "trace/breakpoint trap"),
+    ("SIGABRT", "aborted (abort() or failed assertion)"),
+    ("SIGEMT", "EMT trap"),
+    ("SIGFPE", "floating point exception"),
+    ("SIGKILL", "killed (e.g. by launchd, jetsam or kill -9)"),
+    ("SIGBUS", "bus error (bad memory access)"),
+    ("SIGSEGV", "segmentation fault (bad memory access)"),
+    ("SIGSYS", "bad system call"),
+    ("SIGPIPE", "broken pipe"),
+    ("SIGALRM", "alarm clock"),
+    ("SIGTERM", "terminated (e.g. by launchctl bootout/stop)"),
+    ("SIGURG", "urgent I/O condition"),
+    ("SIGSTOP", "stopped"),
+    ("SIGTSTP", "stopped from terminal"),
+    ("SIGCONT", "continued"),
+    ("SIGCHLD", "child status changed"),
+    ("SIGTTIN", "background read from terminal"),
+    ("SIGTTOU", "background write to terminal"),
+    ("SIGIO", "I/O possible"),
+    ("SIGXCPU", "CPU time limit exceeded"),
+    ("SIGXFSZ", "file size limit exceeded"),
+    ("SIGVTALRM", "virtual timer expired"),
+    ("SIGPROF", "profiling timer expired"),
+    ("SIGWINCH", "window size changed"),
+    ("SIGINFO", "information request"),
+    ("SIGUSR1", "user-defined signal 1"),
+    ("SIGUSR2", "user-defined signal 2"),
+];
+
+/// `<sysexits.h>` codes 64..=78.
+const SYSEXITS: [(&str, &str); 15] = [
+    ("EX_USAGE", "command line usage error"),
+    ("EX_DATAERR", "data format error"),
+    ("EX_NOINPUT", "cannot open input"),
+    ("EX_NOUSER", "addressee unknown"),
+    ("EX_NOHOST", "host name unknown"),
+    ("EX_UNAVAILABLE", "service unavailable"),
+    ("EX_SOFTWARE", "internal software error"),
+    ("EX_OSERR", "system error (e.g. can't fork)"),
+    ("EX_OSFILE", "critical OS file missing"),
+    ("EX_CANTCREAT", "can't create (user) output file"),
+    ("EX_IOERR", "input/output error"),
+    ("EX_TEMPFAIL", "temporary failure, try again later"),
+    ("EX_PROTOCOL", "remote error in protocol"),
+    ("EX_NOPERM", "permission denied"),
+    (
+        "EX_CONFIG",
+        "configuration error: launchd reports this when the program could not be started, \
+         e.g. a missing executable, a wrong path in ProgramArguments or a script without \
+         execute permission",
+    ),
+];
+
+/// Error numbers launchctl prints for failed bootstrap/bootout/kickstart operations
+/// (see `launchctl error <n>`). They also show up as a job's status when spawning failed.
+fn launchd_error(code: i32) -> Option<(&'static str, &'static str)> {
+    match code {
+        3 => Some(("ESRCH", "No such process: the service is not loaded")),
+        5 => Some((
+            "EIO",
+            "Input/output error: launchd's generic bootstrap failure, usually an invalid \
+             plist, wrong file ownership/permissions, or a service that is already loaded",
+        )),
+        37 => Some((
+            "EALREADY",
+            "Operation already in progress: the service is already loaded or starting",
+        )),
+        113 => Some(("ENOSERVICE", "Could not find specified service")),
+        119 => Some(("ESERVICEDISABLED", "Service is disabled")),
+        _ => None,
+    }
+}
+
+fn signal(n: i32) -> Option<(&'static str, &'static str)> {
+    usize::try_from(n - 1)
+        .ok()
+        .and_then(|i| SIGNALS.get(i))
+        .copied()
+}
+
+fn info(
+    code: i32,
+    category: ExitCodeCategory,
+    name: Option<&str>,
+    description: String,
+) -> ExitCodeInfo {
+    ExitCodeInfo {
+        code,
+        category,
+        name: name.map(String::from),
+        description,
+    }
+}
+
+/// Explains a status value from `launchctl list`: negative values are the signal that killed
+/// the process, positive values its exit code.
+pub fn decode(code: i32) -> ExitCodeInfo {
+    if code == 0 {
+        return info(
+            code,
+            ExitCodeCategory::Success,
+            None,
+            "Exited successfully".to_string(),
+        );
+    }
+    if code < 0 {
+        return match signal(-code) {
+            Some((name, desc)) => info(
+                code,
+                ExitCodeCategory::Signal,
+                Some(name),
+                format!("Terminated by {name}: {desc}"),
+            ),
+            None => info(
+                code,
+                ExitCodeCategory::Signal,
+                None,
+                format!("Terminated by signal {}", -code),
+            ),
+        };
+    }
+    if (64..=78).contains(&code) {
+        let (name, desc) = SYSEXITS[(code - 64) as usize];
+        return info(
+            code,
+            ExitCodeCategory::Sysexits,
+            Some(name),
+            format!("{name}: {desc}"),
+        );
+    }
+    if code == 126 {
+        return info(
+            code,
+            ExitCodeCategory::Shell,
+            None,
+            "Command found but not executable (check the execute permission)".to_string(),
+        );
+    }
+    if code == 127 {
+        return info(
+            code,
+            ExitCodeCategory::Shell,
+            None,
+            "Command not found (check the path and PATH in EnvironmentVariables)".to_string(),
+        );
+    }
+    if code > 128 {
+        if let Some((name, desc)) = signal(code - 128) {
+            return info(
+                code,
+                ExitCodeCategory::Shell,
+                Some(name),
+                format!("A child of the shell was terminated by {name}: {desc}"),
+            );
+        }
+    }
+    if let Some((name, desc)) = launchd_error(code) {
+        return info(
+            code,
+            ExitCodeCategory::Launchd,
+            Some(name),
+            desc.to_string(),
+        );
+    }
+    if code == 1 {
+        return info(
+            code,
+            ExitCodeCategory::Generic,
+            None,
+            "General error: check the job's stderr log".to_string(),
+        );
+    }
+    info(
+        code,
+        ExitCodeCategory::Generic,
+        None,
+        format!("Exited with status {code}"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_signals() {
+        let kill = decode(-9);
+        assert_eq!(kill.category, ExitCodeCategory::Signal);
+        assert_eq!(kill.name.as_deref(), Some("SIGKILL"));
+        assert_eq!(decode(-15).name.as_deref(), Some("SIGTERM"));
+        assert_eq!(decode(-99).name, None);
+    }
+
+    #[test]
+    fn test_decode_sysexits() {
+        let config = decode(78);
+        assert_eq!(config.category, ExitCodeCategory::Sysexits);
+        assert_eq!(config.name.as_deref(), Some("EX_CONFIG"));
+        assert_eq!(decode(64).name.as_deref(), Some("EX_USAGE"));
+        assert_eq!(decode(77).name.as_deref(), Some("EX_NOPERM"));
+    }
+
+    #[test]
+    fn test_decode_shell_conventions() {
+        assert_eq!(decode(126).category, ExitCodeCategory::Shell);
+        assert!(decode(127).description.contains("not found"));
+        assert_eq!(decode(137).name.as_deref(), Some("SIGKILL"));
+    }
+
+    #[test]
+    fn test_decode_launchd_errors() {
+        for code in [5, 37, 119] {
+            assert_eq!(decode(code).category, ExitCodeCategory::Launchd);
+        }
+        assert!(decode(119).description.contains("disabled"));
+    }
+
+    #[test]
+    fn test_decode_generic() {
+        assert_eq!(decode(0).category, ExitCodeCategory::Success);
+        assert_eq!(decode(1).category, ExitCodeCategory::Generic);
+        assert_eq!(decode(42).description, "Exited with status 42");
+    }
+}
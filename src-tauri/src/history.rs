@@ -0,0 +1,248 @@
+use crate::error::AppError;
+use crate::launchctl::LoadedService;
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const HISTORY_DIR: &str = "history";
+const SNAPSHOT_FILE: &str = "snapshot.json";
+const EVENTS_FILE: &str = "events.jsonl";
+
+/// Serializes snapshot read-compare-write cycles between concurrent `list_jobs` calls.
+static RECORD_LOCK: Mutex<()> = Mutex::new(());
+
+/// What `launchctl list` reported for one service at one point in time.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ServiceState {
+    pub pid: Option<u32>,
+    pub last_exit_code: Option<i32>,
+}
+
+/// Loaded services keyed by label.
+pub type Snapshot = HashMap<String, ServiceState>;
+
+pub fn snapshot_from(loaded: &[LoadedService]) -> Snapshot {
+    loaded
+        .iter()
+        .map(|s| {
+            (
+                s.label.clone(),
+                ServiceState {
+                    pid: s.pid,
+                    last_exit_code: s.last_exit_code,
+                },
+            )
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum RunEventKind {
+    Started,
+    Exited,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RunEvent {
+    pub label: String,
+    pub kind: RunEventKind,
+    pub pid: u32,
+    /// Exit status reported by launchd once the process is gone. `None` for start events and
+    /// for runs that ended with the job being unloaded.
+    pub exit_code: Option<i32>,
+    /// Epoch milliseconds at which the change was observed.
+    pub at: i64,
+}
+
+/// Time range filter for `get_job_history`, in epoch milliseconds. Both ends are inclusive.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HistoryRange {
+    pub start: Option<i64>,
+    pub end: Option<i64>,
+}
+
+/// Derives start/end events from two consecutive snapshots. A PID that appears is a start;
+/// a PID that disappears (or is replaced by a new one) is an end, carrying the exit code
+/// launchd reports in the newer snapshot.
+pub fn diff_snapshots(prev: &Snapshot, current: &Snapshot, at: i64) -> Vec<RunEvent> {
+    let mut events = Vec::new();
+    for (label, old) in prev {
+        let Some(old_pid) = old.pid else {
+            continue;
+        };
+        let new = current.get(label);
+        if new.and_then(|n| n.pid) == Some(old_pid) {
+            continue;
+        }
+        events.push(RunEvent {
+            label: label.clone(),
+            kind: RunEventKind::Exited,
+            pid: old_pid,
+            exit_code: new.and_then(|n| n.last_exit_code),
+            at,
+        });
+    }
+    for (label, new) in current {
+        let Some(new_pid) = new.pid else {
+            continue;
+        };
+        if prev.get(label).and_then(|p| p.pid) == Some(new_pid) {
+            continue;
+        }
+        events.push(RunEvent {
+            label: label.clone(),
+            kind: RunEventKind::Started,
+            pid: new_pid,
+            exit_code: None,
+            at,
+        });
+    }
+    // Exits before starts so a PID replacement reads in order; then by label for stable output.
+    events.sort_by(|a, b| {
+        (a.kind == RunEventKind::Started, &a.label)
+            .cmp(&(b.kind == RunEventKind::Started, &b.label))
+    });
+    events
+}
+
+fn history_dir() -> Result<PathBuf, AppError> {
+    storage::app_data_subdir(HISTORY_DIR)
+}
+
+fn record_in(dir: &Path, current: &Snapshot, at: i64) -> Result<Vec<RunEvent>, AppError> {
+    let snapshot_path = dir.join(SNAPSHOT_FILE);
+    // Without a previous snapshot there is nothing to compare against: record nothing rather
+    // than reporting every running job as freshly started.
+    let events = match storage::read_json::<Snapshot>(&snapshot_path)? {
+        Some(prev) => diff_snapshots(&prev, current, at),
+        None => Vec::new(),
+    };
+    storage::append_jsonl(&dir.join(EVENTS_FILE), &events)?;
+    storage::write_json(&snapshot_path, current)?;
+    Ok(events)
+}
+
+/// Compares the services `launchctl list` reports now with the previous snapshot, appends
+/// the resulting start/end events and stores the new snapshot.
+pub fn record(loaded: &[LoadedService]) -> Result<Vec<RunEvent>, AppError> {
+    let _guard = RECORD_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    record_in(
+        &history_dir()?,
+        &snapshot_from(loaded),
+        storage::current_millis(),
+    )
+}
+
+fn events_in(dir: &Path) -> Result<Vec<RunEvent>, AppError> {
+    storage::read_jsonl(&dir.join(EVENTS_FILE))
+}
+
+/// Recorded events for `label` within `range`, oldest first.
+pub fn job_history(label: &str, range: &HistoryRange) -> Result<Vec<RunEvent>, AppError> {
+    Ok(events_in(&history_dir()?)?
+        .into_iter()
+        .filter(|e| e.label == label)
+        .filter(|e| range.start.is_none_or(|s| e.at >= s))
+        .filter(|e| range.end.is_none_or(|end| e.at <= end))
+        .collect())
+}
+
+/// All recorded events, oldest first. Best-effort: an unreadable store yields no events.
+pub fn load_events() -> Vec<RunEvent> {
+    history_dir()
+        .and_then(|dir| events_in(&dir))
+        .unwrap_or_default()
+}
+
+/// Most recent observed start time per label, in epoch milliseconds.
+pub fn last_started_at(events: &[RunEvent]) -> HashMap<String, i64> {
+    let mut latest: HashMap<String, i64> = HashMap::new();
+    for e in events.iter().filter(|e| e.kind == RunEventKind::Started) {
+        let at = latest.entry(e.label.clone()).or_insert(e.at);
+        *at = (*at).max(e.at);
+    }
+    latest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snap(entries: &[(&str, Option<u32>, Option<i32>)]) -> Snapshot {
+        entries
+            .iter()
+            .map(|(label, pid, code)| {
+                (
+                    label.to_string(),
+                    ServiceState {
+                        pid: *pid,
+                        last_exit_code: *code,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_start_and_exit() {
+        let prev = snap(&[("a", None, Some(0)), ("b", Some(10), Some(0))]);
+        let current = snap(&[("a", Some(20), Some(0)), ("b", None, Some(1))]);
+        let events = diff_snapshots(&prev, &current, 5);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].label, "b");
+        assert_eq!(events[0].kind, RunEventKind::Exited);
+        assert_eq!(events[0].exit_code, Some(1));
+        assert_eq!(events[1].label, "a");
+        assert_eq!(events[1].kind, RunEventKind::Started);
+        assert_eq!(events[1].pid, 20);
+        assert_eq!(events[1].at, 5);
+    }
+
+    #[test]
+    fn test_diff_pid_replaced_and_unloaded() {
+        let prev = snap(&[("a", Some(10), None), ("b", Some(11), None)]);
+        let current = snap(&[("a", Some(12), Some(-9))]);
+        let events = diff_snapshots(&prev, &current, 0);
+        let summary: Vec<(&str, RunEventKind, u32, Option<i32>)> = events
+            .iter()
+            .map(|e| (e.label.as_str(), e.kind, e.pid, e.exit_code))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("a", RunEventKind::Exited, 10, Some(-9)),
+                ("b", RunEventKind::Exited, 11, None),
+                ("a", RunEventKind::Started, 12, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_unchanged_is_empty() {
+        let s = snap(&[("a", Some(10), None), ("b", None, Some(0))]);
+        assert!(diff_snapshots(&s, &s, 0).is_empty());
+    }
+
+    #[test]
+    fn test_record_in_persists_events() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = snap(&[("a", Some(1), None)]);
+        // First observation only stores the snapshot.
+        assert!(record_in(dir.path(), &first, 100).unwrap().is_empty());
+
+        let second = snap(&[("a", None, Some(78))]);
+        let events = record_in(dir.path(), &second, 200).unwrap();
+        assert_eq!(events.len(), 1);
+
+        let third = snap(&[("a", Some(2), Some(78))]);
+        record_in(dir.path(), &third, 300).unwrap();
+
+        let stored = events_in(dir.path()).unwrap();
+        assert_eq!(stored.len(), 2);
+        assert_eq!(stored[0].exit_code, Some(78));
+        assert_eq!(stored[1].kind, RunEventKind::Started);
+        assert_eq!(stored[1].at, 300);
+    }
+}
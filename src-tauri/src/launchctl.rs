@@ -1,4 +1,4 @@
-use crate::error::AppError;
+use crate::error::{AppError, LaunchctlFailure};
 use std::process::Command;
 
 fn get_uid() -> u32 {
@@ -51,112 +51,126 @@ pub fn parse_list_output(output: &str) -> Vec<LoadedService> {
     services
 }
 
-pub fn list_loaded() -> Result<Vec<LoadedService>, AppError> {
+/// Runs `launchctl <args>`, turning a non-zero exit into a classified [`AppError`].
+fn run(operation: &str, target: &str, args: &[&str]) -> Result<String, AppError> {
     let output = Command::new("launchctl")
-        .arg("list")
+        .arg(operation)
+        .args(args)
         .output()
-        .map_err(|e| AppError::Launchctl(format!("failed to run launchctl list: {e}")))?;
+        .map_err(|e| {
+            AppError::Launchctl(LaunchctlFailure {
+                operation: operation.to_string(),
+                target: target.to_string(),
+                stderr: e.to_string(),
+                hint: "Could not run launchctl.".to_string(),
+            })
+        })?;
 
     if !output.status.success() {
-        return Err(AppError::Launchctl(format!(
-            "launchctl list failed: {}",
-            String::from_utf8_lossy(&output.stderr)
-        )));
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        // launchctl prints some errors (e.g. for bootout) on stdout.
+        let message = if stderr.trim().is_empty() {
+            String::from_utf8_lossy(&output.stdout)
+        } else {
+            stderr
+        };
+        return Err(AppError::from_launchctl(operation, target, &message));
     }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
+pub fn list_loaded() -> Result<Vec<LoadedService>, AppError> {
+    let stdout = run("list", &gui_target(), &[])?;
     Ok(parse_list_output(&stdout))
 }
 
 pub fn bootstrap(plist_path: &str) -> Result<(), AppError> {
-    let output = Command::new("launchctl")
-        .args(["bootstrap", &gui_target(), plist_path])
-        .output()
-        .map_err(|e| AppError::Launchctl(format!("failed to run launchctl bootstrap: {e}")))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-        // "service already loaded" is not a fatal error
-        if stderr.contains("already loaded") || stderr.contains("service already loaded") {
-            return Ok(());
-        }
-        let hint = if stderr.contains("Input/output error") {
-            " Try re-running the command as root for richer errors."
-        } else {
-            ""
-        };
-        return Err(AppError::Launchctl(format!(
-            "Bootstrap failed for {plist_path}: {stderr}{hint}"
-        )));
+    match run("bootstrap", plist_path, &[&gui_target(), plist_path]) {
+        Err(AppError::ServiceAlreadyLoaded(_)) => Ok(()),
+        other => other.map(|_| ()),
     }
-    Ok(())
 }
 
 pub fn bootout(plist_path: &str) -> Result<(), AppError> {
-    let output = Command::new("launchctl")
-        .args(["bootout", &gui_target(), plist_path])
-        .output()
-        .map_err(|e| AppError::Launchctl(format!("failed to run launchctl bootout: {e}")))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        // "not loaded" is not a fatal error
-        if stderr.contains("not loaded")
-            || stderr.contains("No such process")
-            || stderr.contains("Could not find specified service")
-        {
-            return Ok(());
-        }
-        return Err(AppError::Launchctl(format!(
-            "launchctl bootout failed: {stderr}"
-        )));
+    match run("bootout", plist_path, &[&gui_target(), plist_path]) {
+        Err(AppError::ServiceNotFound(_)) => Ok(()),
+        other => other.map(|_| ()),
     }
-    Ok(())
 }
 
 pub fn kickstart(label: &str) -> Result<(), AppError> {
-    let output = Command::new("launchctl")
-        .args(["kickstart", "-k", &service_target(label)])
-        .output()
-        .map_err(|e| AppError::Launchctl(format!("failed to run launchctl kickstart: {e}")))?;
-
-    if !output.status.success() {
-        return Err(AppError::Launchctl(format!(
-            "launchctl kickstart failed: {}",
-            String::from_utf8_lossy(&output.stderr)
-        )));
-    }
-    Ok(())
+    let target = service_target(label);
+    run("kickstart", &target, &["-k", &target]).map(|_| ())
 }
 
 pub fn enable(label: &str) -> Result<(), AppError> {
-    let output = Command::new("launchctl")
-        .args(["enable", &service_target(label)])
-        .output()
-        .map_err(|e| AppError::Launchctl(format!("failed to run launchctl enable: {e}")))?;
-
-    if !output.status.success() {
-        return Err(AppError::Launchctl(format!(
-            "launchctl enable failed: {}",
-            String::from_utf8_lossy(&output.stderr)
-        )));
-    }
-    Ok(())
+    let target = service_target(label);
+    run("enable", &target, &[&target]).map(|_| ())
 }
 
 pub fn disable(label: &str) -> Result<(), AppError> {
-    let output = Command::new("launchctl")
-        .args(["disable", &service_target(label)])
-        .output()
-        .map_err(|e| AppError::Launchctl(format!("failed to run launchctl disable: {e}")))?;
+    let target = service_target(label);
+    run("disable", &target, &[&target]).map(|_| ())
+}
 
-    if !output.status.success() {
-        return Err(AppError::Launchctl(format!(
-            "launchctl disable failed: {}",
-            String::from_utf8_lossy(&output.stderr)
-        )));
+/// Top-level fields of `launchctl print <service-target>` that describe the job's runs.
+#[derive(Debug, Default, PartialEq)]
+pub struct ServiceDetails {
+    pub state: Option<String>,
+    pub pid: Option<u32>,
+    pub runs: Option<u64>,
+    /// e.g. `78: EX_CONFIG`
+    pub last_exit_code: Option<String>,
+    /// e.g. `Killed: 9`
+    pub last_terminating_signal: Option<String>,
+}
+
+impl ServiceDetails {
+    /// launchd reports `spawn scheduled` while it holds back a respawn (throttling).
+    pub fn is_throttled(&self) -> bool {
+        self.state
+            .as_deref()
+            .is_some_and(|s| s.contains("spawn scheduled") || s.contains("throttled"))
+    }
+}
+
+pub fn parse_print_output(output: &str) -> ServiceDetails {
+    let mut details = ServiceDetails::default();
+    let mut depth = 0usize;
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if trimmed.ends_with('{') {
+            depth += 1;
+            continue;
+        }
+        if trimmed == "}" {
+            depth = depth.saturating_sub(1);
+            continue;
+        }
+        // Only the service's own fields; nested blocks (endpoints, environment) are skipped.
+        if depth != 1 {
+            continue;
+        }
+        let Some((key, value)) = trimmed.split_once(" = ") else {
+            continue;
+        };
+        let value = value.trim().to_string();
+        match key {
+            "state" => details.state = Some(value),
+            "pid" => details.pid = value.parse().ok(),
+            "runs" => details.runs = value.parse().ok(),
+            "last exit code" => details.last_exit_code = Some(value),
+            "last terminating signal" => details.last_terminating_signal = Some(value),
+            _ => {}
+        }
     }
-    Ok(())
+    details
+}
+
+pub fn print(label: &str) -> Result<ServiceDetails, AppError> {
+    let target = service_target(label);
+    let stdout = run("print", &target, &[&target])?;
+    Ok(parse_print_output(&stdout))
 }
 
 #[cfg(test)]
@@ -196,4 +210,31 @@ mod tests {
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].label, "com.example.test");
     }
+
+    #[test]
+    fn test_parse_print_output() {
+        let output = "gui/501/com.example.sync = {\n\
+                      \tactive count = 0\n\
+                      \tpath = /Users/x/Library/LaunchAgents/com.example.sync.plist\n\
+                      \tstate = spawn scheduled\n\
+                      \n\
+                      \tenvironment = {\n\
+                      \t\truns = not-a-field\n\
+                      \t}\n\
+                      \n\
+                      \truns = 14\n\
+                      \tlast exit code = 78: EX_CONFIG\n\
+                      \tlast terminating signal = Killed: 9\n\
+                      }\n";
+        let details = parse_print_output(output);
+        assert_eq!(details.state, Some("spawn scheduled".to_string()));
+        assert_eq!(details.runs, Some(14));
+        assert_eq!(details.last_exit_code, Some("78: EX_CONFIG".to_string()));
+        assert_eq!(
+            details.last_terminating_signal,
+            Some("Killed: 9".to_string())
+        );
+        assert_eq!(details.pid, None);
+        assert!(details.is_throttled());
+    }
 }
@@ -1,15 +1,31 @@
 mod commands;
+mod crash_loop;
+mod dry_run;
 mod error;
+mod exit_code;
+mod history;
 mod launchctl;
+mod logs;
+mod monitor;
+mod newsyslog;
 mod plist_util;
+mod storage;
 mod types;
+mod unified_log;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
+        .setup(|app| {
+            monitor::spawn(app.handle().clone());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             commands::list_jobs,
             commands::get_job_detail,
+            commands::get_job_history,
+            commands::set_monitor_interval,
+            commands::get_monitor_interval,
             commands::start_job,
             commands::stop_job,
             commands::restart_job,
@@ -20,9 +36,16 @@ pub fn run() {
             commands::save_raw_plist,
             commands::create_job,
             commands::delete_job,
+            commands::dry_run_job,
             commands::read_log_file,
+            commands::read_merged_logs,
+            commands::get_log_stats,
+            commands::get_unified_log,
             commands::clear_log_file,
             commands::open_log_in_editor,
+            commands::preview_log_rotation,
+            commands::write_log_rotation,
+            commands::get_log_rotation_status,
             commands::get_home_dir,
             commands::reveal_in_finder,
         ])
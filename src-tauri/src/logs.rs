@@ -0,0 +1,595 @@
+use chrono::{DateTime, Datelike, Local, NaiveDateTime, TimeZone};
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+pub enum LogStream {
+    Stdout,
+    Stderr,
+}
+
+/// One line of the merged stdout/stderr timeline.
+#[derive(Debug, Clone, Serialize)]
+pub struct TimelineLine {
+    pub stream: LogStream,
+    /// 1-based line number within the source file.
+    pub line_number: usize,
+    /// Milliseconds since the epoch, as a string for JS `Date` (same as `LogFileResult`).
+    /// `None` when the line itself carries no timestamp.
+    pub timestamp: Option<String>,
+    pub text: String,
+}
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+fn local_millis(naive: NaiveDateTime) -> Option<i64> {
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|dt| dt.timestamp_millis())
+}
+
+/// ISO-8601 / RFC 3339, with either `T` or a space between date and time. Timestamps without
+/// an offset are taken as local time, which is what launchd jobs usually print.
+fn parse_iso(line: &str) -> Option<i64> {
+    let bytes = line.as_bytes();
+    if bytes.len() < 19
+        || !bytes[..4].iter().all(u8::is_ascii_digit)
+        || bytes[4] != b'-'
+        || bytes[7] != b'-'
+        || !(bytes[10] == b'T' || bytes[10] == b' ')
+    {
+        return None;
+    }
+    let end = line[11..]
+        .find(|c: char| c.is_whitespace() || c == ']')
+        .map_or(line.len(), |i| i + 11);
+    let candidate = format!("{}T{}", &line[..10], &line[11..end]);
+
+    if let Ok(dt) = DateTime::parse_from_rfc3339(&candidate) {
+        return Some(dt.timestamp_millis());
+    }
+    if let Ok(dt) = DateTime::parse_from_str(&candidate, "%Y-%m-%dT%H:%M:%S%.f%z") {
+        return Some(dt.timestamp_millis());
+    }
+    // Python's logging module uses a comma before the milliseconds.
+    let candidate = candidate.replacen(',', ".", 1);
+    NaiveDateTime::parse_from_str(&candidate, "%Y-%m-%dT%H:%M:%S%.f")
+        .ok()
+        .and_then(local_millis)
+}
+
+/// BSD syslog style `Jan  2 03:04:05`. The year is missing, so it is taken from `reference`
+/// (the file's modification time) and rolled back a year for dates after it.
+fn parse_syslog(line: &str, reference: DateTime<Local>) -> Option<i64> {
+    let mut parts = line.split_whitespace();
+    let month = parts.next()?;
+    let month = MONTHS.iter().position(|m| *m == month)? as u32 + 1;
+    let day: u32 = parts.next()?.parse().ok()?;
+    let time = parts.next()?;
+    if time.len() < 8 || time.as_bytes()[2] != b':' {
+        return None;
+    }
+    let time = chrono::NaiveTime::parse_from_str(&time[..8], "%H:%M:%S").ok()?;
+
+    let year = reference.year();
+    let date = chrono::NaiveDate::from_ymd_opt(year, month, day)?;
+    let millis = local_millis(date.and_time(time))?;
+    if millis > reference.timestamp_millis() + 86_400_000 {
+        let date = chrono::NaiveDate::from_ymd_opt(year - 1, month, day)?;
+        return local_millis(date.and_time(time));
+    }
+    Some(millis)
+}
+
+/// Unix epoch seconds (10 digits, optional fraction) or milliseconds (13 digits).
+fn parse_epoch(line: &str) -> Option<i64> {
+    let token = line.split_whitespace().next()?;
+    let (int, frac) = token.split_once('.').unwrap_or((token, ""));
+    if !int.bytes().all(|b| b.is_ascii_digit()) || !frac.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let value: i64 = int.parse().ok()?;
+    match int.len() {
+        10 => {
+            let frac_ms: i64 = format!("{frac:0<3}")[..3].parse().unwrap_or(0);
+            Some(value * 1000 + frac_ms)
+        }
+        13 if frac.is_empty() => Some(value),
+        _ => None,
+    }
+}
+
+/// Detects a leading timestamp in one of the common formats and returns it in epoch millis.
+pub fn parse_timestamp(line: &str, reference: DateTime<Local>) -> Option<i64> {
+    let trimmed = line.trim_start().trim_start_matches('[');
+    parse_iso(trimmed)
+        .or_else(|| parse_syslog(trimmed, reference))
+        .or_else(|| parse_epoch(trimmed))
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+pub enum LogFormat {
+    JsonLines,
+    Logfmt,
+    PlainText,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, PartialOrd)]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warning,
+    Error,
+    Fatal,
+}
+
+/// A log line broken into its structured parts.
+#[derive(Debug, Clone, Serialize)]
+pub struct ParsedLogLine {
+    /// Milliseconds since the epoch, as a string for JS `Date`.
+    pub timestamp: Option<String>,
+    pub level: Option<LogLevel>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LogStats {
+    pub format: LogFormat,
+    pub error_count: usize,
+    pub warning_count: usize,
+}
+
+const TIMESTAMP_KEYS: [&str; 6] = ["time", "timestamp", "ts", "@timestamp", "datetime", "date"];
+const LEVEL_KEYS: [&str; 6] = [
+    "level",
+    "severity",
+    "lvl",
+    "levelname",
+    "log.level",
+    "loglevel",
+];
+const MESSAGE_KEYS: [&str; 4] = ["msg", "message", "text", "event"];
+
+/// Number of non-empty lines sampled from the top of a file to pick its format.
+const FORMAT_SAMPLE_LINES: usize = 50;
+
+fn level_from_str(s: &str) -> Option<LogLevel> {
+    match s.to_ascii_lowercase().as_str() {
+        "trace" | "verbose" => Some(LogLevel::Trace),
+        "debug" | "dbug" => Some(LogLevel::Debug),
+        "info" | "information" | "notice" | "default" => Some(LogLevel::Info),
+        "warn" | "warning" => Some(LogLevel::Warning),
+        "error" | "err" => Some(LogLevel::Error),
+        "fatal" | "critical" | "crit" | "panic" | "emerg" | "alert" => Some(LogLevel::Fatal),
+        _ => None,
+    }
+}
+
+/// Numeric levels as used by pino/bunyan (10 trace ... 60 fatal).
+fn level_from_number(n: i64) -> Option<LogLevel> {
+    match n {
+        10 => Some(LogLevel::Trace),
+        20 => Some(LogLevel::Debug),
+        30 => Some(LogLevel::Info),
+        40 => Some(LogLevel::Warning),
+        50 => Some(LogLevel::Error),
+        60 => Some(LogLevel::Fatal),
+        _ => None,
+    }
+}
+
+/// Epoch numbers in structured logs are seconds (possibly fractional) or milliseconds.
+fn epoch_number_millis(n: f64) -> i64 {
+    if n > 1e12 {
+        n as i64
+    } else {
+        (n * 1000.0) as i64
+    }
+}
+
+/// Splits a logfmt line (`key=value key="quoted value"`) into pairs. Returns `None` unless every
+/// token is a pair and there are at least two, so prose with a stray `=` is not mistaken for it.
+fn parse_logfmt(line: &str) -> Option<Vec<(String, String)>> {
+    let mut pairs = Vec::new();
+    let mut chars = line.trim().chars().peekable();
+    while chars.peek().is_some() {
+        while chars.peek().is_some_and(|c| c.is_whitespace()) {
+            chars.next();
+        }
+        let mut key = String::new();
+        while let Some(c) = chars.next_if(|c| *c != '=' && !c.is_whitespace()) {
+            key.push(c);
+        }
+        if key.is_empty() || chars.next() != Some('=') {
+            return None;
+        }
+        let mut value = String::new();
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            let mut escaped = false;
+            for c in chars.by_ref() {
+                match c {
+                    '\\' if !escaped => escaped = true,
+                    '"' if !escaped => break,
+                    _ => {
+                        escaped = false;
+                        value.push(c);
+                    }
+                }
+            }
+        } else {
+            value = chars.by_ref().take_while(|c| !c.is_whitespace()).collect();
+        }
+        pairs.push((key, value));
+    }
+    (pairs.len() >= 2).then_some(pairs)
+}
+
+fn looks_like_json(line: &str) -> bool {
+    line.trim_start().starts_with('{')
+        && serde_json::from_str::<JsonValue>(line).is_ok_and(|v| v.is_object())
+}
+
+/// Picks the dominant format of the first non-empty lines of a file. A format wins when at
+/// least half of the sampled lines parse as it.
+pub fn detect_format(content: &str) -> LogFormat {
+    let sample: Vec<&str> = content
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .take(FORMAT_SAMPLE_LINES)
+        .collect();
+    if sample.is_empty() {
+        return LogFormat::PlainText;
+    }
+    let threshold = sample.len().div_ceil(2);
+    if sample.iter().filter(|l| looks_like_json(l)).count() >= threshold {
+        LogFormat::JsonLines
+    } else if sample.iter().filter(|l| parse_logfmt(l).is_some()).count() >= threshold {
+        LogFormat::Logfmt
+    } else {
+        LogFormat::PlainText
+    }
+}
+
+fn parse_json_line(line: &str, reference: DateTime<Local>) -> Option<ParsedLogLine> {
+    let value: JsonValue = serde_json::from_str(line).ok()?;
+    let obj = value.as_object()?;
+    let field = |keys: &[&str]| keys.iter().find_map(|k| obj.get(*k));
+
+    let timestamp = field(&TIMESTAMP_KEYS).and_then(|v| match v {
+        JsonValue::String(s) => parse_timestamp(s, reference),
+        JsonValue::Number(n) => n.as_f64().map(epoch_number_millis),
+        _ => None,
+    });
+    let level = field(&LEVEL_KEYS).and_then(|v| match v {
+        JsonValue::String(s) => level_from_str(s),
+        JsonValue::Number(n) => n.as_i64().and_then(level_from_number),
+        _ => None,
+    });
+    let message = match field(&MESSAGE_KEYS) {
+        Some(JsonValue::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+        None => line.to_string(),
+    };
+    Some(ParsedLogLine {
+        timestamp: timestamp.map(|t| t.to_string()),
+        level,
+        message,
+    })
+}
+
+fn parse_logfmt_line(line: &str, reference: DateTime<Local>) -> Option<ParsedLogLine> {
+    let pairs = parse_logfmt(line)?;
+    let field = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|k| pairs.iter().find(|(key, _)| key == k).map(|(_, v)| v))
+    };
+    let timestamp = field(&TIMESTAMP_KEYS).and_then(|v| {
+        parse_timestamp(v, reference).or_else(|| v.parse::<f64>().ok().map(epoch_number_millis))
+    });
+    Some(ParsedLogLine {
+        timestamp: timestamp.map(|t| t.to_string()),
+        level: field(&LEVEL_KEYS).and_then(|v| level_from_str(v)),
+        message: field(&MESSAGE_KEYS)
+            .cloned()
+            .unwrap_or_else(|| line.to_string()),
+    })
+}
+
+/// Finds a level keyword in free text: an all-caps word (`ERROR`, `[WARN]`) or a
+/// `error:` / `Warning:` prefix, as printed by most CLIs and runtimes.
+fn detect_plain_level(line: &str) -> Option<LogLevel> {
+    for word in line.split(|c: char| !c.is_ascii_alphabetic()) {
+        if word.len() >= 3 && word.bytes().all(|b| b.is_ascii_uppercase()) {
+            if let Some(level) = level_from_str(word) {
+                return Some(level);
+            }
+        }
+    }
+    line.split_whitespace()
+        .take(4)
+        .filter_map(|w| w.strip_suffix(':'))
+        .find_map(level_from_str)
+}
+
+/// Extracts timestamp, level and message from one line in the given format. Lines that do
+/// not match a structured format fall back to plain-text heuristics.
+pub fn parse_line(line: &str, format: LogFormat, reference: DateTime<Local>) -> ParsedLogLine {
+    let structured = match format {
+        LogFormat::JsonLines => parse_json_line(line, reference),
+        LogFormat::Logfmt => parse_logfmt_line(line, reference),
+        LogFormat::PlainText => None,
+    };
+    structured.unwrap_or_else(|| ParsedLogLine {
+        timestamp: parse_timestamp(line, reference).map(|t| t.to_string()),
+        level: detect_plain_level(line),
+        message: line.to_string(),
+    })
+}
+
+/// Detects the file's format and counts error (including fatal) and warning lines.
+pub fn compute_stats(content: &str, reference: DateTime<Local>) -> LogStats {
+    let format = detect_format(content);
+    let mut stats = LogStats {
+        format,
+        error_count: 0,
+        warning_count: 0,
+    };
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        match parse_line(line, format, reference).level {
+            Some(LogLevel::Error | LogLevel::Fatal) => stats.error_count += 1,
+            Some(LogLevel::Warning) => stats.warning_count += 1,
+            _ => {}
+        }
+    }
+    stats
+}
+
+struct KeyedLine {
+    key: Option<i64>,
+    line: TimelineLine,
+}
+
+/// Tags each line of a file and assigns it a sort key: its own timestamp, or the one of the
+/// nearest preceding timestamped line (so stack traces and wrapped output stay attached).
+/// Lines before the first timestamp borrow the first one.
+fn key_lines(content: &str, stream: LogStream, reference: DateTime<Local>) -> Vec<KeyedLine> {
+    let mut lines: Vec<KeyedLine> = content
+        .lines()
+        .enumerate()
+        .map(|(i, text)| {
+            let ts = parse_timestamp(text, reference);
+            KeyedLine {
+                key: ts,
+                line: TimelineLine {
+                    stream,
+                    line_number: i + 1,
+                    timestamp: ts.map(|t| t.to_string()),
+                    text: text.to_string(),
+                },
+            }
+        })
+        .collect();
+
+    let first = lines.iter().find_map(|l| l.key);
+    let mut current = first;
+    for l in lines.iter_mut() {
+        match l.key {
+            Some(k) => current = Some(k),
+            None => l.key = current,
+        }
+    }
+    lines
+}
+
+/// Merges stdout and stderr into one timeline ordered by timestamp. Each file's own line
+/// order is always preserved; a file without any timestamps is placed after the other.
+pub fn merge_streams(stdout: &str, stderr: &str, reference: DateTime<Local>) -> Vec<TimelineLine> {
+    let out = key_lines(stdout, LogStream::Stdout, reference);
+    let err = key_lines(stderr, LogStream::Stderr, reference);
+
+    let mut merged = Vec::with_capacity(out.len() + err.len());
+    let mut out = out.into_iter().peekable();
+    let mut err = err.into_iter().peekable();
+    loop {
+        let take_out = match (out.peek(), err.peek()) {
+            (None, None) => break,
+            (Some(_), None) => true,
+            (None, Some(_)) => false,
+            (Some(o), Some(e)) => match (o.key, e.key) {
+                (Some(ok), Some(ek)) => ok <= ek,
+                (Some(_), None) => true,
+                (None, _) => false,
+            },
+        };
+        let next = if take_out { out.next() } else { err.next() };
+        merged.extend(next.map(|k| k.line));
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reference() -> DateTime<Local> {
+        Local.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap()
+    }
+
+    fn local(y: i32, mo: u32, d: u32, h: u32, mi: u32, s: u32) -> i64 {
+        Local
+            .with_ymd_and_hms(y, mo, d, h, mi, s)
+            .unwrap()
+            .timestamp_millis()
+    }
+
+    #[test]
+    fn test_parse_iso_variants() {
+        let r = reference();
+        assert_eq!(
+            parse_timestamp("2024-05-01T10:00:00Z started", r),
+            Some(1_714_557_600_000)
+        );
+        assert_eq!(
+            parse_timestamp("2024-05-01T12:00:00.250+02:00 started", r),
+            Some(1_714_557_600_250)
+        );
+        assert_eq!(
+            parse_timestamp("[2024-05-01 10:00:00] started", r),
+            Some(local(2024, 5, 1, 10, 0, 0))
+        );
+        assert_eq!(
+            parse_timestamp("2024-05-01 10:00:00,500 INFO root: hi", r),
+            Some(local(2024, 5, 1, 10, 0, 0) + 500)
+        );
+        assert_eq!(
+            parse_timestamp("2024-05-01 10:00:00.000000+0000 0x1 Default", r),
+            Some(1_714_557_600_000)
+        );
+    }
+
+    #[test]
+    fn test_parse_syslog_uses_reference_year() {
+        let r = reference();
+        assert_eq!(
+            parse_timestamp("May  1 10:00:00 host job[12]: hi", r),
+            Some(local(2024, 5, 1, 10, 0, 0))
+        );
+        // December is after the June reference date, so it must be last year's.
+        assert_eq!(
+            parse_timestamp("Dec 31 23:59:59 host job[12]: hi", r),
+            Some(local(2023, 12, 31, 23, 59, 59))
+        );
+    }
+
+    #[test]
+    fn test_parse_epoch() {
+        let r = reference();
+        assert_eq!(parse_timestamp("1714557600 hi", r), Some(1_714_557_600_000));
+        assert_eq!(
+            parse_timestamp("1714557600.5 hi", r),
+            Some(1_714_557_600_500)
+        );
+        assert_eq!(
+            parse_timestamp("1714557600123 hi", r),
+            Some(1_714_557_600_123)
+        );
+        assert_eq!(parse_timestamp("12345 items processed", r), None);
+        assert_eq!(parse_timestamp("plain text", r), None);
+    }
+
+    #[test]
+    fn test_merge_interleaves_by_timestamp() {
+        let stdout = "2024-05-01T10:00:00Z start\n2024-05-01T10:00:02Z done\n";
+        let stderr = "2024-05-01T10:00:01Z warning\n";
+        let merged = merge_streams(stdout, stderr, reference());
+        let texts: Vec<&str> = merged.iter().map(|l| l.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "2024-05-01T10:00:00Z start",
+                "2024-05-01T10:00:01Z warning",
+                "2024-05-01T10:00:02Z done",
+            ]
+        );
+        assert_eq!(merged[1].stream, LogStream::Stderr);
+    }
+
+    #[test]
+    fn test_merge_keeps_continuation_lines_with_their_entry() {
+        let stdout = "2024-05-01T10:00:00Z a\n2024-05-01T10:00:05Z b\n";
+        let stderr = "2024-05-01T10:00:01Z Traceback:\n  File \"x.py\"\nValueError\n";
+        let merged = merge_streams(stdout, stderr, reference());
+        let streams: Vec<LogStream> = merged.iter().map(|l| l.stream).collect();
+        assert_eq!(
+            streams,
+            vec![
+                LogStream::Stdout,
+                LogStream::Stderr,
+                LogStream::Stderr,
+                LogStream::Stderr,
+                LogStream::Stdout,
+            ]
+        );
+        assert_eq!(merged[2].timestamp, None);
+        assert_eq!(merged[2].line_number, 2);
+    }
+
+    #[test]
+    fn test_merge_untimestamped_file_goes_last() {
+        let merged = merge_streams("no time\nat all\n", "2024-05-01T10:00:00Z x\n", reference());
+        assert_eq!(merged[0].stream, LogStream::Stderr);
+        assert_eq!(merged[1].text, "no time");
+        assert_eq!(merged[2].text, "at all");
+    }
+
+    #[test]
+    fn test_detect_format() {
+        let json = "{\"level\":\"info\",\"msg\":\"a\"}\n{\"level\":\"error\",\"msg\":\"b\"}\n";
+        assert_eq!(detect_format(json), LogFormat::JsonLines);
+        let logfmt =
+            "time=2024-05-01T10:00:00Z level=info msg=\"started job\"\nlevel=warn msg=slow\n";
+        assert_eq!(detect_format(logfmt), LogFormat::Logfmt);
+        assert_eq!(
+            detect_format("hello world\nx=1 only\n"),
+            LogFormat::PlainText
+        );
+        assert_eq!(detect_format(""), LogFormat::PlainText);
+    }
+
+    #[test]
+    fn test_parse_json_line() {
+        let r = reference();
+        let line = r#"{"ts":1714557600.5,"level":"warning","msg":"disk almost full"}"#;
+        let parsed = parse_line(line, LogFormat::JsonLines, r);
+        assert_eq!(parsed.timestamp, Some("1714557600500".to_string()));
+        assert_eq!(parsed.level, Some(LogLevel::Warning));
+        assert_eq!(parsed.message, "disk almost full");
+
+        // pino numeric level with millisecond epoch
+        let line = r#"{"level":50,"time":1714557600000,"msg":"boom"}"#;
+        let parsed = parse_line(line, LogFormat::JsonLines, r);
+        assert_eq!(parsed.level, Some(LogLevel::Error));
+        assert_eq!(parsed.timestamp, Some("1714557600000".to_string()));
+
+        // A non-JSON line in a JSON-lines file falls back to plain text.
+        let parsed = parse_line("panic: ERROR in main", LogFormat::JsonLines, r);
+        assert_eq!(parsed.level, Some(LogLevel::Error));
+    }
+
+    #[test]
+    fn test_parse_logfmt_line() {
+        let line = r#"time=2024-05-01T10:00:00Z level=error msg="could not \"connect\"" retry=3"#;
+        let parsed = parse_line(line, LogFormat::Logfmt, reference());
+        assert_eq!(parsed.timestamp, Some("1714557600000".to_string()));
+        assert_eq!(parsed.level, Some(LogLevel::Error));
+        assert_eq!(parsed.message, "could not \"connect\"");
+    }
+
+    #[test]
+    fn test_plain_level_detection() {
+        let r = reference();
+        let level = |l: &str| parse_line(l, LogFormat::PlainText, r).level;
+        assert_eq!(
+            level("2024-05-01 10:00:00 [WARN] retrying"),
+            Some(LogLevel::Warning)
+        );
+        assert_eq!(level("error: no such file"), Some(LogLevel::Error));
+        assert_eq!(level("Error: ENOENT"), Some(LogLevel::Error));
+        assert_eq!(level("processed 3 items without error"), None);
+        assert_eq!(level("INFO starting"), Some(LogLevel::Info));
+    }
+
+    #[test]
+    fn test_compute_stats() {
+        let content = "INFO start\nWARN slow\nERROR failed\nFATAL crash\n\nplain\n";
+        let stats = compute_stats(content, reference());
+        assert_eq!(stats.format, LogFormat::PlainText);
+        assert_eq!(stats.error_count, 2);
+        assert_eq!(stats.warning_count, 1);
+    }
+}
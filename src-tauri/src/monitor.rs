@@ -0,0 +1,273 @@
+use crate::crash_loop;
+use crate::history::{self, RunEventKind, Snapshot};
+use crate::launchctl;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+const DEFAULT_INTERVAL_SECS: u64 = 5;
+
+/// Seconds between `launchctl list` samples. 0 pauses the monitor.
+static INTERVAL_SECS: AtomicU64 = AtomicU64::new(DEFAULT_INTERVAL_SECS);
+
+pub fn set_interval(secs: u64) {
+    INTERVAL_SECS.store(secs, Ordering::Relaxed);
+}
+
+pub fn interval() -> u64 {
+    INTERVAL_SECS.load(Ordering::Relaxed)
+}
+
+/// A state change observed between two samples. Serialized without a tag: the Tauri event
+/// name (see [`MonitorEvent::name`]) already says which variant it is.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum MonitorEvent {
+    Started {
+        label: String,
+        pid: u32,
+    },
+    Exited {
+        label: String,
+        pid: u32,
+        exit_code: Option<i32>,
+    },
+    Loaded {
+        label: String,
+    },
+    Unloaded {
+        label: String,
+    },
+    CrashLoop {
+        label: String,
+        exits: usize,
+        window_secs: u64,
+    },
+}
+
+impl MonitorEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            MonitorEvent::Started { .. } => "job-started",
+            MonitorEvent::Exited { .. } => "job-exited",
+            MonitorEvent::Loaded { .. } => "job-loaded",
+            MonitorEvent::Unloaded { .. } => "job-unloaded",
+            MonitorEvent::CrashLoop { .. } => "job-crash-loop",
+        }
+    }
+}
+
+/// Remembers recent abnormal exits per label to spot jobs that keep dying.
+#[derive(Default)]
+pub struct CrashLoopTracker {
+    exits: HashMap<String, VecDeque<i64>>,
+}
+
+impl CrashLoopTracker {
+    /// Records an exit and returns the number of abnormal exits in the window once it reaches
+    /// the threshold. The count is reset afterwards so one loop is reported once per window.
+    fn record_exit(&mut self, label: &str, exit_code: Option<i32>, at: i64) -> Option<usize> {
+        if exit_code.is_none_or(|c| c == 0) {
+            return None;
+        }
+        let exits = self.exits.entry(label.to_string()).or_default();
+        exits.push_back(at);
+        while exits
+            .front()
+            .is_some_and(|t| at - t > crash_loop::WINDOW_MS)
+        {
+            exits.pop_front();
+        }
+        if exits.len() >= crash_loop::RESTART_THRESHOLD {
+            let count = exits.len();
+            exits.clear();
+            return Some(count);
+        }
+        None
+    }
+}
+
+/// Compares two samples and returns the events to emit, in a stable order: unloads, exits,
+/// crash loops, loads, then starts.
+pub fn compare(
+    prev: &Snapshot,
+    current: &Snapshot,
+    at: i64,
+    tracker: &mut CrashLoopTracker,
+) -> Vec<MonitorEvent> {
+    let mut events = Vec::new();
+
+    let mut unloaded: Vec<&String> = prev.keys().filter(|l| !current.contains_key(*l)).collect();
+    unloaded.sort();
+    events.extend(unloaded.into_iter().map(|label| MonitorEvent::Unloaded {
+        label: label.clone(),
+    }));
+
+    let mut starts = Vec::new();
+    let mut crash_loops = Vec::new();
+    for run in history::diff_snapshots(prev, current, at) {
+        match run.kind {
+            RunEventKind::Exited => {
+                if let Some(exits) = tracker.record_exit(&run.label, run.exit_code, at) {
+                    crash_loops.push(MonitorEvent::CrashLoop {
+                        label: run.label.clone(),
+                        exits,
+                        window_secs: (crash_loop::WINDOW_MS / 1000) as u64,
+                    });
+                }
+                events.push(MonitorEvent::Exited {
+                    label: run.label,
+                    pid: run.pid,
+                    exit_code: run.exit_code,
+                });
+            }
+            RunEventKind::Started => starts.push(MonitorEvent::Started {
+                label: run.label,
+                pid: run.pid,
+            }),
+        }
+    }
+    events.extend(crash_loops);
+
+    let mut loaded: Vec<&String> = current.keys().filter(|l| !prev.contains_key(*l)).collect();
+    loaded.sort();
+    events.extend(loaded.into_iter().map(|label| MonitorEvent::Loaded {
+        label: label.clone(),
+    }));
+    events.extend(starts);
+    events
+}
+
+fn sleep_interval() {
+    let started = Instant::now();
+    loop {
+        std::thread::sleep(Duration::from_millis(250));
+        let secs = interval();
+        if secs != 0 && started.elapsed() >= Duration::from_secs(secs) {
+            return;
+        }
+    }
+}
+
+/// Starts the background sampling thread. Events are emitted to all windows; samples are
+/// also recorded into the run history so it stays complete while the UI is idle.
+pub fn spawn(app: AppHandle) {
+    std::thread::spawn(move || {
+        let mut prev: Option<Snapshot> = None;
+        let mut tracker = CrashLoopTracker::default();
+        loop {
+            if let Ok(loaded) = launchctl::list_loaded() {
+                let _ = history::record(&loaded);
+                let current = history::snapshot_from(&loaded);
+                if let Some(ref prev) = prev {
+                    let at = crate::storage::current_millis();
+                    for event in compare(prev, &current, at, &mut tracker) {
+                        let _ = app.emit(event.name(), event);
+                    }
+                }
+                prev = Some(current);
+            }
+            sleep_interval();
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::ServiceState;
+
+    fn snap(entries: &[(&str, Option<u32>, Option<i32>)]) -> Snapshot {
+        entries
+            .iter()
+            .map(|(label, pid, code)| {
+                (
+                    label.to_string(),
+                    ServiceState {
+                        pid: *pid,
+                        last_exit_code: *code,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_compare_load_start_exit_unload() {
+        let mut tracker = CrashLoopTracker::default();
+        let prev = snap(&[("gone", None, Some(0)), ("running", Some(10), None)]);
+        let current = snap(&[("running", None, Some(1)), ("new", Some(20), None)]);
+        let events = compare(&prev, &current, 0, &mut tracker);
+        assert_eq!(
+            events,
+            vec![
+                MonitorEvent::Unloaded {
+                    label: "gone".to_string()
+                },
+                MonitorEvent::Exited {
+                    label: "running".to_string(),
+                    pid: 10,
+                    exit_code: Some(1)
+                },
+                MonitorEvent::Loaded {
+                    label: "new".to_string()
+                },
+                MonitorEvent::Started {
+                    label: "new".to_string(),
+                    pid: 20
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compare_no_change() {
+        let mut tracker = CrashLoopTracker::default();
+        let s = snap(&[("a", Some(1), None)]);
+        assert!(compare(&s, &s, 0, &mut tracker).is_empty());
+    }
+
+    #[test]
+    fn test_crash_loop_detected_once() {
+        let mut tracker = CrashLoopTracker::default();
+        let mut prev = snap(&[("a", Some(100), None)]);
+        let mut loops = 0;
+        for i in 1..=(crash_loop::RESTART_THRESHOLD as u32 + 2) {
+            let current = snap(&[("a", Some(100 + i), Some(78))]);
+            let events = compare(&prev, &current, i64::from(i) * 10_000, &mut tracker);
+            loops += events
+                .iter()
+                .filter(|e| e.name() == "job-crash-loop")
+                .count();
+            prev = current;
+        }
+        assert_eq!(loops, 1);
+    }
+
+    #[test]
+    fn test_crash_loop_ignores_clean_and_slow_exits() {
+        let mut tracker = CrashLoopTracker::default();
+        for i in 0..10 {
+            assert_eq!(tracker.record_exit("clean", Some(0), i), None);
+            assert_eq!(
+                tracker.record_exit("slow", Some(1), i * crash_loop::WINDOW_MS),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn test_event_payload_is_untagged() {
+        let event = MonitorEvent::Exited {
+            label: "a".to_string(),
+            pid: 1,
+            exit_code: Some(-9),
+        };
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"label":"a","pid":1,"exit_code":-9}"#
+        );
+    }
+}
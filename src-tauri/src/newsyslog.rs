@@ -0,0 +1,381 @@
+use crate::error::AppError;
+use crate::types::PlistConfig;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+const NEWSYSLOG_CONF: &str = "/etc/newsyslog.conf";
+const NEWSYSLOG_DIR: &str = "/etc/newsyslog.d";
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum Compression {
+    None,
+    Gzip,
+    Bzip2,
+    Xz,
+}
+
+impl Compression {
+    fn flag(self) -> Option<char> {
+        match self {
+            Compression::None => None,
+            Compression::Gzip => Some('Z'),
+            Compression::Bzip2 => Some('J'),
+            Compression::Xz => Some('X'),
+        }
+    }
+}
+
+/// User-selected rotation policy for a job's log files. At least one of `size_kb` or `when`
+/// should be set, otherwise newsyslog never rotates the file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RotationOptions {
+    /// `owner:group` for the recreated log file. Defaults to the current log file's uid:gid
+    /// so a user agent keeps writing to a file it owns after rotation.
+    pub owner: Option<String>,
+    /// Octal file mode, e.g. `644`.
+    pub mode: Option<String>,
+    /// Number of archives to keep.
+    pub count: u32,
+    /// Rotate once the file exceeds this many kilobytes.
+    pub size_kb: Option<u64>,
+    /// newsyslog `when` field: an hour interval (`24`), `@T00` ISO-8601 or `$D0` day/week/month.
+    pub when: Option<String>,
+    pub compression: Compression,
+}
+
+/// A single line from a newsyslog configuration file.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NewsyslogEntry {
+    pub log_path: String,
+    pub owner: Option<String>,
+    pub mode: String,
+    pub count: u32,
+    pub size_kb: Option<u64>,
+    pub when: Option<String>,
+    pub flags: String,
+    pub pid_file: Option<String>,
+    pub signal: Option<String>,
+    /// The configuration file this entry was read from.
+    pub source: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LogRotationStatus {
+    pub log_path: String,
+    pub covered_by: Option<NewsyslogEntry>,
+}
+
+/// Log paths configured for a job, de-duplicated (stdout and stderr often share a file).
+pub fn job_log_paths(config: &PlistConfig) -> Vec<String> {
+    let mut paths: Vec<String> = Vec::new();
+    for path in [&config.standard_out_path, &config.standard_error_path]
+        .into_iter()
+        .flatten()
+    {
+        if !paths.contains(path) {
+            paths.push(path.clone());
+        }
+    }
+    paths
+}
+
+fn default_owner(log_path: &str) -> Option<String> {
+    use std::os::unix::fs::MetadataExt;
+    let metadata = std::fs::metadata(log_path).ok()?;
+    Some(format!("{}:{}", metadata.uid(), metadata.gid()))
+}
+
+fn format_entry(log_path: &str, options: &RotationOptions) -> String {
+    let owner = options
+        .owner
+        .clone()
+        .or_else(|| default_owner(log_path))
+        .unwrap_or_default();
+    let mode = options.mode.as_deref().unwrap_or("644");
+    let size = options
+        .size_kb
+        .map_or_else(|| "*".to_string(), |s| s.to_string());
+    let when = options.when.as_deref().unwrap_or("*");
+    // N: launchd jobs have no pid file to signal. The job reopens its log on next launch.
+    let mut flags = String::from("N");
+    if let Some(flag) = options.compression.flag() {
+        flags.push(flag);
+    }
+    format!(
+        "{log_path}\t{owner}\t{mode}\t{}\t{size}\t{when}\t{flags}",
+        options.count
+    )
+}
+
+/// Builds the contents of a `newsyslog.d` file rotating the job's stdout/stderr logs.
+pub fn generate_config(
+    config: &PlistConfig,
+    options: &RotationOptions,
+) -> Result<String, AppError> {
+    let paths = job_log_paths(config);
+    if paths.is_empty() {
+        return Err(AppError::Plist(format!(
+            "{} has no StandardOutPath or StandardErrorPath to rotate",
+            config.label
+        )));
+    }
+    if options.size_kb.is_none() && options.when.is_none() {
+        return Err(AppError::Plist(
+            "a size or time threshold is required for log rotation".to_string(),
+        ));
+    }
+
+    let mut out = format!(
+        "# Log rotation for {}, generated by launchd-ui\n\
+         # logfilename\t[owner:group]\tmode\tcount\tsize\twhen\tflags\n",
+        config.label
+    );
+    for path in paths {
+        out.push_str(&format_entry(&path, options));
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+fn is_owner_field(field: &str) -> bool {
+    field.contains(':') || (field.contains('.') && !field.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Parses newsyslog configuration text. Comments, blank lines and `<include>` directives are
+/// skipped; malformed lines are ignored rather than failing the whole file.
+pub fn parse_config(content: &str, source: Option<&str>) -> Vec<NewsyslogEntry> {
+    let mut entries = Vec::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with('<') {
+            continue;
+        }
+        let mut fields = line.split_whitespace().peekable();
+        let Some(log_path) = fields.next() else {
+            continue;
+        };
+        let owner = match fields.peek() {
+            Some(f) if is_owner_field(f) => fields.next().map(String::from),
+            _ => None,
+        };
+        let (Some(mode), Some(count), Some(size), Some(when)) =
+            (fields.next(), fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        let Ok(count) = count.parse::<u32>() else {
+            continue;
+        };
+        let flags = fields.next().unwrap_or("-");
+        let pid_file = fields.next().map(String::from);
+        let signal = fields.next().map(String::from);
+
+        entries.push(NewsyslogEntry {
+            log_path: log_path.to_string(),
+            owner,
+            mode: mode.to_string(),
+            count,
+            size_kb: size.parse::<u64>().ok(),
+            when: (when != "*").then(|| when.to_string()),
+            flags: if flags == "-" {
+                String::new()
+            } else {
+                flags.to_string()
+            },
+            pid_file,
+            signal,
+            source: source.map(String::from),
+        });
+    }
+    entries
+}
+
+/// Reads `/etc/newsyslog.conf` and every file under `/etc/newsyslog.d`.
+pub fn scan_existing() -> Vec<NewsyslogEntry> {
+    let mut files = vec![PathBuf::from(NEWSYSLOG_CONF)];
+    if let Ok(entries) = std::fs::read_dir(NEWSYSLOG_DIR) {
+        let mut extra: Vec<PathBuf> = entries.flatten().map(|e| e.path()).collect();
+        extra.sort();
+        files.extend(extra);
+    }
+
+    let mut entries = Vec::new();
+    for file in files {
+        if let Ok(content) = std::fs::read_to_string(&file) {
+            entries.extend(parse_config(&content, file.to_str()));
+        }
+    }
+    entries
+}
+
+/// Shell-style wildcard match supporting `*` and `?`, used for entries with the `G` flag.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+    let (mut pi, mut ti) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some((pi, ti));
+            pi += 1;
+        } else if let Some((sp, st)) = star {
+            pi = sp + 1;
+            ti = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|&c| c == '*')
+}
+
+fn entry_covers(entry: &NewsyslogEntry, log_path: &str) -> bool {
+    if entry.flags.contains('G') {
+        glob_match(&entry.log_path, log_path)
+    } else {
+        entry.log_path == log_path
+    }
+}
+
+/// Reports, for each of the job's log files, the first newsyslog entry that rotates it.
+pub fn coverage(config: &PlistConfig, entries: &[NewsyslogEntry]) -> Vec<LogRotationStatus> {
+    job_log_paths(config)
+        .into_iter()
+        .map(|log_path| {
+            let covered_by = entries.iter().find(|e| entry_covers(e, &log_path)).cloned();
+            LogRotationStatus {
+                log_path,
+                covered_by,
+            }
+        })
+        .collect()
+}
+
+pub fn config_path_for(label: &str) -> PathBuf {
+    Path::new(NEWSYSLOG_DIR).join(format!("{label}.conf"))
+}
+
+/// Writes the generated configuration to `/etc/newsyslog.d/<label>.conf`. That directory is
+/// root-owned, so this usually fails with a permission error unless the app runs as root.
+pub fn write_config(label: &str, content: &str) -> Result<String, AppError> {
+    let path = config_path_for(label);
+    std::fs::write(&path, content).map_err(|e| {
+        if e.kind() == std::io::ErrorKind::PermissionDenied {
+            AppError::Io(std::io::Error::new(
+                e.kind(),
+                format!(
+                    "cannot write {}: permission denied. Copy the preview into place with sudo instead.",
+                    path.display()
+                ),
+            ))
+        } else {
+            AppError::Io(e)
+        }
+    })?;
+    Ok(path.to_string_lossy().into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cfg(out: Option<&str>, err: Option<&str>) -> PlistConfig {
+        PlistConfig {
+            label: "com.example.logs".to_string(),
+            program: None,
+            program_arguments: None,
+            run_at_load: None,
+            keep_alive: None,
+            start_interval: None,
+            start_calendar_interval: None,
+            standard_out_path: out.map(String::from),
+            standard_error_path: err.map(String::from),
+            working_directory: None,
+            environment_variables: None,
+            disabled: None,
+            wake_system: None,
+            raw_xml: String::new(),
+        }
+    }
+
+    fn options() -> RotationOptions {
+        RotationOptions {
+            owner: Some("501:20".to_string()),
+            mode: None,
+            count: 5,
+            size_kb: Some(1024),
+            when: None,
+            compression: Compression::Gzip,
+        }
+    }
+
+    #[test]
+    fn test_generate_config_dedupes_shared_log() {
+        let config = cfg(Some("/tmp/job.log"), Some("/tmp/job.log"));
+        let out = generate_config(&config, &options()).unwrap();
+        let lines: Vec<&str> = out.lines().filter(|l| !l.starts_with('#')).collect();
+        assert_eq!(lines, vec!["/tmp/job.log\t501:20\t644\t5\t1024\t*\tNZ"]);
+    }
+
+    #[test]
+    fn test_generate_config_requires_threshold() {
+        let config = cfg(Some("/tmp/out.log"), None);
+        let mut opts = options();
+        opts.size_kb = None;
+        assert!(generate_config(&config, &opts).is_err());
+        assert!(generate_config(&cfg(None, None), &options()).is_err());
+    }
+
+    #[test]
+    fn test_generated_config_parses_back() {
+        let config = cfg(Some("/tmp/out.log"), Some("/tmp/err.log"));
+        let mut opts = options();
+        opts.when = Some("@T00".to_string());
+        opts.compression = Compression::Bzip2;
+        let out = generate_config(&config, &opts).unwrap();
+        let entries = parse_config(&out, None);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].log_path, "/tmp/err.log");
+        assert_eq!(entries[1].owner, Some("501:20".to_string()));
+        assert_eq!(entries[1].count, 5);
+        assert_eq!(entries[1].size_kb, Some(1024));
+        assert_eq!(entries[1].when, Some("@T00".to_string()));
+        assert_eq!(entries[1].flags, "NJ");
+    }
+
+    #[test]
+    fn test_parse_system_config() {
+        let content = "# logfilename          [owner:group]    mode count size when  flags [/pid_file] [sig_num]\n\
+                       <include> /etc/newsyslog.d/*\n\
+                       /var/log/install.log\t\t\t644  10\t   1000\t*     J\n\
+                       /var/log/wtmp\t\t\t\t644  3\t   *\t@01T05 B\n\
+                       /var/log/ppp.log\troot:wheel\t640  3\t   100\t*     JN /var/run/ppp.pid 30\n";
+        let entries = parse_config(content, Some(NEWSYSLOG_CONF));
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].owner, None);
+        assert_eq!(entries[0].size_kb, Some(1000));
+        assert_eq!(entries[1].size_kb, None);
+        assert_eq!(entries[1].when, Some("@01T05".to_string()));
+        assert_eq!(entries[2].owner, Some("root:wheel".to_string()));
+        assert_eq!(entries[2].pid_file, Some("/var/run/ppp.pid".to_string()));
+        assert_eq!(entries[2].signal, Some("30".to_string()));
+        assert_eq!(entries[2].source, Some(NEWSYSLOG_CONF.to_string()));
+    }
+
+    #[test]
+    fn test_coverage_exact_and_glob() {
+        let entries = parse_config(
+            "/tmp/out.log\t644\t5\t100\t*\tN\n/Users/x/logs/*.log\t644\t5\t100\t*\tGN\n",
+            None,
+        );
+        let config = cfg(Some("/tmp/out.log"), Some("/Users/x/logs/err.log"));
+        let status = coverage(&config, &entries);
+        assert!(status.iter().all(|s| s.covered_by.is_some()));
+
+        let uncovered = cfg(Some("/tmp/other.log"), None);
+        assert!(coverage(&uncovered, &entries)[0].covered_by.is_none());
+    }
+}
@@ -0,0 +1,77 @@
+use crate::error::AppError;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Same identifier as `tauri.conf.json`, so this matches Tauri's `app_data_dir()`
+/// (`~/Library/Application Support/com.github.azu.launchd-ui` on macOS).
+const APP_IDENTIFIER: &str = "com.github.azu.launchd-ui";
+
+/// Directory for state the app keeps between runs. Created on first use.
+pub fn app_data_dir() -> Result<PathBuf, AppError> {
+    let dir = dirs::data_dir()
+        .ok_or_else(|| AppError::NotFound("could not determine app data directory".to_string()))?
+        .join(APP_IDENTIFIER);
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// A subdirectory of the app data directory, created on first use.
+pub fn app_data_subdir(name: &str) -> Result<PathBuf, AppError> {
+    let dir = app_data_dir()?.join(name);
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+pub fn current_millis() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as i64)
+}
+
+pub fn read_json<T: DeserializeOwned>(path: &Path) -> Result<Option<T>, AppError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let data = std::fs::read(path)?;
+    serde_json::from_slice(&data)
+        .map(Some)
+        .map_err(|e| AppError::Io(std::io::Error::other(format!("{}: {e}", path.display()))))
+}
+
+pub fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), AppError> {
+    let data = serde_json::to_vec_pretty(value).map_err(|e| AppError::Io(e.into()))?;
+    std::fs::write(path, data)?;
+    Ok(())
+}
+
+/// Appends records to a JSON-lines file, one object per line.
+pub fn append_jsonl<T: Serialize>(path: &Path, records: &[T]) -> Result<(), AppError> {
+    if records.is_empty() {
+        return Ok(());
+    }
+    let mut buf = Vec::new();
+    for record in records {
+        serde_json::to_writer(&mut buf, record).map_err(|e| AppError::Io(e.into()))?;
+        buf.push(b'\n');
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    file.write_all(&buf)?;
+    Ok(())
+}
+
+/// Reads a JSON-lines file. Lines that fail to parse (e.g. a torn final write) are skipped.
+pub fn read_jsonl<T: DeserializeOwned>(path: &Path) -> Result<Vec<T>, AppError> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(path)?;
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
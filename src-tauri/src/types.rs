@@ -14,6 +14,33 @@ pub enum JobStatus {
     Loaded,
     Unloaded,
     Unknown,
+    /// Loaded, but keeps exiting abnormally and being restarted (or throttled) by launchd.
+    CrashLooping,
+}
+
+/// Which convention an exit status was interpreted under.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ExitCodeCategory {
+    Success,
+    /// Killed by a signal (negative status in `launchctl list`).
+    Signal,
+    /// A `<sysexits.h>` code such as `EX_CONFIG` (78).
+    Sysexits,
+    /// A shell convention: 126/127, or 128 + signal number.
+    Shell,
+    /// A launchd/launchctl error number (see `launchctl error <n>`).
+    Launchd,
+    Generic,
+}
+
+/// Human-readable explanation of a job's last exit status.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExitCodeInfo {
+    pub code: i32,
+    pub category: ExitCodeCategory,
+    /// Symbolic name, e.g. `SIGKILL` or `EX_CONFIG`.
+    pub name: Option<String>,
+    pub description: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,9 +48,13 @@ pub struct JobListEntry {
     pub label: String,
     pub pid: Option<u32>,
     pub last_exit_code: Option<i32>,
+    /// Decoded `last_exit_code`.
+    pub last_exit_info: Option<ExitCodeInfo>,
     pub plist_path: String,
     pub source: JobSource,
     pub status: JobStatus,
+    /// Why the job has its status, when that needs explaining (e.g. `CrashLooping`).
+    pub status_reason: Option<String>,
     pub last_run_at: Option<String>,
     /// True when this looks like a user-authored automation (a script under the home
     /// directory), as opposed to a vendor-installed app. Drives the "Home" filter.
@@ -63,8 +94,10 @@ pub struct LaunchdJob {
     pub plist_path: String,
     pub source: JobSource,
     pub status: JobStatus,
+    pub status_reason: Option<String>,
     pub pid: Option<u32>,
     pub last_exit_code: Option<i32>,
+    pub last_exit_info: Option<ExitCodeInfo>,
     pub plist: PlistConfig,
     pub last_run_at: Option<String>,
 }
@@ -96,9 +129,11 @@ mod tests {
             label: "com.example.test".to_string(),
             pid: Some(1234),
             last_exit_code: Some(0),
+            last_exit_info: None,
             plist_path: "/Users/test/Library/LaunchAgents/com.example.test.plist".to_string(),
             source: JobSource::UserAgent,
             status: JobStatus::Running,
+            status_reason: None,
             last_run_at: None,
             is_home_agent: false,
         };
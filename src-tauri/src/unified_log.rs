@@ -0,0 +1,244 @@
+use crate::error::AppError;
+use crate::logs;
+use serde::{Deserialize, Serialize};
+use std::process::Command;
+
+/// launchd's own subsystem in the unified log.
+const LAUNCHD_SUBSYSTEM: &str = "com.apple.xpc.launchd";
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum UnifiedLogLevel {
+    Debug,
+    Info,
+    Default,
+    Error,
+    Fault,
+}
+
+/// What a launchd message says about the job, for the messages worth highlighting.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+pub enum LaunchdEventKind {
+    /// launchd could not exec the program (missing binary, bad permissions, sandbox).
+    SpawnFailed,
+    /// The job exited with a non-zero code or was killed by a signal.
+    AbnormalExit,
+    /// The job exited cleanly.
+    Exited,
+    /// launchd delayed the next respawn because the job died too quickly.
+    Throttled,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UnifiedLogEntry {
+    /// Milliseconds since the epoch, as a string for JS `Date`.
+    pub timestamp: Option<String>,
+    pub level: UnifiedLogLevel,
+    pub process: String,
+    pub pid: Option<u32>,
+    pub subsystem: Option<String>,
+    pub category: Option<String>,
+    pub message: String,
+    /// True when the message was written by launchd rather than the job itself.
+    pub from_launchd: bool,
+    pub kind: Option<LaunchdEventKind>,
+}
+
+/// The subset of `log show --style ndjson` fields we use.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawEntry {
+    timestamp: Option<String>,
+    event_message: Option<String>,
+    event_type: Option<String>,
+    message_type: Option<UnifiedLogLevel>,
+    process_image_path: Option<String>,
+    #[serde(rename = "processID")]
+    process_id: Option<u32>,
+    subsystem: Option<String>,
+    category: Option<String>,
+}
+
+/// Quotes a string for use inside an NSPredicate.
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Builds a `log show` predicate matching launchd's messages about `label` and, when a PID
+/// is given, everything that process logged itself.
+pub fn build_predicate(label: &str, pid: Option<u32>) -> String {
+    let launchd = format!(
+        "(subsystem == {} AND eventMessage CONTAINS {})",
+        quote(LAUNCHD_SUBSYSTEM),
+        quote(label)
+    );
+    match pid {
+        Some(pid) => format!("{launchd} OR processIdentifier == {pid}"),
+        None => launchd,
+    }
+}
+
+/// Classifies a launchd message. The wording comes from launchd on macOS 13-15.
+pub fn classify_message(message: &str) -> Option<LaunchdEventKind> {
+    if message.contains("Could not find and/or execute program")
+        || message.contains("Service could not initialize")
+        || message.contains("spawn failed")
+        || message.contains("failed to spawn")
+    {
+        Some(LaunchdEventKind::SpawnFailed)
+    } else if message.contains("Pushing respawn out by") || message.contains("respawn throttled") {
+        Some(LaunchdEventKind::Throttled)
+    } else if message.contains("exited with abnormal code")
+        || message.contains("exited due to")
+        || message
+            .split_once("exited with exit code: ")
+            .is_some_and(|(_, code)| code.trim() != "0")
+    {
+        Some(LaunchdEventKind::AbnormalExit)
+    } else if message.contains("exited with exit code: 0") {
+        Some(LaunchdEventKind::Exited)
+    } else {
+        None
+    }
+}
+
+/// Parses `log show --style ndjson` output. Non-JSON lines, the trailing summary object and
+/// non-log events (activities, signposts) are skipped.
+pub fn parse_ndjson(output: &str) -> Vec<UnifiedLogEntry> {
+    let reference = chrono::Local::now();
+    output
+        .lines()
+        .filter_map(|line| serde_json::from_str::<RawEntry>(line).ok())
+        .filter(|raw| raw.event_type.as_deref().is_none_or(|t| t == "logEvent"))
+        .filter_map(|raw| {
+            let message = raw.event_message?;
+            let process_path = raw.process_image_path.unwrap_or_default();
+            let process = process_path
+                .rsplit('/')
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            let subsystem = raw.subsystem.filter(|s| !s.is_empty());
+            let from_launchd =
+                subsystem.as_deref() == Some(LAUNCHD_SUBSYSTEM) || process == "launchd";
+            let kind = if from_launchd {
+                classify_message(&message)
+            } else {
+                None
+            };
+            Some(UnifiedLogEntry {
+                timestamp: raw
+                    .timestamp
+                    .as_deref()
+                    .and_then(|t| logs::parse_timestamp(t, reference))
+                    .map(|t| t.to_string()),
+                level: raw.message_type.unwrap_or(UnifiedLogLevel::Default),
+                process,
+                pid: raw.process_id,
+                subsystem,
+                category: raw.category.filter(|c| !c.is_empty()),
+                message,
+                from_launchd,
+                kind,
+            })
+        })
+        .collect()
+}
+
+fn format_log_time(millis: i64) -> Option<String> {
+    chrono::DateTime::from_timestamp_millis(millis).map(|dt| {
+        dt.with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string()
+    })
+}
+
+/// Runs `log show` for the given time range (epoch millis). `end` defaults to now.
+pub fn show(
+    label: &str,
+    pid: Option<u32>,
+    start: i64,
+    end: Option<i64>,
+) -> Result<Vec<UnifiedLogEntry>, AppError> {
+    let start = format_log_time(start)
+        .ok_or_else(|| AppError::NotFound(format!("invalid start time: {start}")))?;
+    let mut cmd = Command::new("log");
+    cmd.args(["show", "--style", "ndjson", "--info", "--start", &start]);
+    if let Some(end) = end.and_then(format_log_time) {
+        cmd.args(["--end", &end]);
+    }
+    cmd.args(["--predicate", &build_predicate(label, pid)]);
+
+    let output = cmd.output()?;
+    if !output.status.success() {
+        return Err(AppError::Io(std::io::Error::other(format!(
+            "log show failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ))));
+    }
+    Ok(parse_ndjson(&String::from_utf8_lossy(&output.stdout)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAUNCHD_FIXTURE: &str = include_str!("../tests/fixtures/unified_log/launchd_job.ndjson");
+    const PROCESS_FIXTURE: &str = include_str!("../tests/fixtures/unified_log/job_process.ndjson");
+
+    #[test]
+    fn test_build_predicate() {
+        assert_eq!(
+            build_predicate("com.example.sync", None),
+            r#"(subsystem == "com.apple.xpc.launchd" AND eventMessage CONTAINS "com.example.sync")"#
+        );
+        assert_eq!(
+            build_predicate("com.example.sync", Some(42)),
+            r#"(subsystem == "com.apple.xpc.launchd" AND eventMessage CONTAINS "com.example.sync") OR processIdentifier == 42"#
+        );
+        assert!(build_predicate("a\"b", None).contains(r#"CONTAINS "a\"b""#));
+    }
+
+    #[test]
+    fn test_parse_launchd_fixture() {
+        let entries = parse_ndjson(LAUNCHD_FIXTURE);
+        assert_eq!(entries.len(), 6);
+        assert!(
+            entries
+                .iter()
+                .all(|e| e.from_launchd && e.process == "launchd")
+        );
+
+        assert_eq!(entries[0].kind, None);
+        assert_eq!(entries[0].timestamp, Some("1714582800120".to_string()));
+        assert_eq!(entries[1].kind, Some(LaunchdEventKind::AbnormalExit));
+        assert_eq!(entries[2].kind, Some(LaunchdEventKind::AbnormalExit));
+        assert_eq!(entries[2].level, UnifiedLogLevel::Error);
+        assert_eq!(entries[3].kind, Some(LaunchdEventKind::Throttled));
+        assert_eq!(entries[4].kind, Some(LaunchdEventKind::SpawnFailed));
+        assert!(entries[4].message.contains("/Users/x/bin/sync.sh"));
+        assert_eq!(entries[5].kind, Some(LaunchdEventKind::AbnormalExit));
+    }
+
+    #[test]
+    fn test_parse_process_fixture() {
+        let entries = parse_ndjson(PROCESS_FIXTURE);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].process, "sync");
+        assert_eq!(entries[0].pid, Some(8123));
+        assert_eq!(entries[0].subsystem, None);
+        assert_eq!(entries[0].level, UnifiedLogLevel::Info);
+        assert!(!entries[0].from_launchd);
+        assert_eq!(entries[1].level, UnifiedLogLevel::Fault);
+        assert_eq!(entries[1].category, Some("net".to_string()));
+        assert_eq!(entries[1].kind, None);
+    }
+
+    #[test]
+    fn test_classify_clean_exit() {
+        assert_eq!(
+            classify_message("[gui/501/com.x [1]]: exited with exit code: 0"),
+            Some(LaunchdEventKind::Exited)
+        );
+        assert_eq!(classify_message("service state changed to running"), None);
+    }
+}
@@ -14,6 +14,7 @@ import {
   saveJob,
   createJob,
   revealInFinder,
+  formatError,
 } from "@/lib/invoke"
 import type { JobListEntry, LaunchdJob, PlistConfig } from "@/types"
 import {
@@ -53,7 +54,7 @@ function App() {
         await action()
         await refresh()
       } catch (e) {
-        setActionError(String(e))
+        setActionError(formatError(e))
       }
     },
     [refresh]
@@ -8,8 +8,10 @@ function job(overrides: Partial<LaunchdJob>): LaunchdJob {
     plist_path: "/Users/test/Library/LaunchAgents/com.example.agent.plist",
     source: "UserAgent",
     status: "Running",
+    status_reason: null,
     pid: 1234,
     last_exit_code: 0,
+    last_exit_info: null,
     last_run_at: null,
     plist: {
       label: "com.example.agent",
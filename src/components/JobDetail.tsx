@@ -71,12 +71,19 @@ export function JobDetail({ plistPath, open, onClose, onEdit }: JobDetailProps)
               >
                 {job.status}
               </Badge>
+              {job.status_reason && (
+                <span className="text-xs text-destructive">{job.status_reason}</span>
+              )}
               {job.pid && (
                 <span className="text-xs text-muted-foreground">PID: {job.pid}</span>
               )}
               {job.last_exit_code !== null && job.last_exit_code !== undefined && (
-                <span className="text-xs text-muted-foreground">
+                <span
+                  className="text-xs text-muted-foreground"
+                  title={job.last_exit_info?.description}
+                >
                   Exit: {job.last_exit_code}
+                  {job.last_exit_info?.name && ` (${job.last_exit_info.name})`}
                 </span>
               )}
               {job.last_run_at && (
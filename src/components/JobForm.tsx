@@ -17,7 +17,7 @@ import {
   SelectValue,
 } from "@/components/ui/select"
 import type { PlistConfig, LaunchdJob, CalendarInterval } from "@/types"
-import { getHomeDir } from "@/lib/invoke"
+import { formatError, getHomeDir } from "@/lib/invoke"
 import {
   detectHourRange,
   expandHourRange,
@@ -183,7 +183,7 @@ export function JobForm({ open, onClose, onSave, editingJob }: JobFormProps) {
       await onSave(finalConfig, editingJob?.plist_path)
       onClose()
     } catch (e) {
-      setError(String(e))
+      setError(formatError(e))
     } finally {
       setSaving(false)
     }
@@ -8,9 +8,11 @@ const mockJobs: JobListEntry[] = [
     label: "com.example.running",
     pid: 1234,
     last_exit_code: 0,
+    last_exit_info: null,
     plist_path: "/Users/test/Library/LaunchAgents/com.example.running.plist",
     source: "UserAgent",
     status: "Running",
+    status_reason: null,
     last_run_at: String(Date.now()),
     is_home_agent: true,
   },
@@ -18,9 +20,16 @@ const mockJobs: JobListEntry[] = [
     label: "com.example.stopped",
     pid: null,
     last_exit_code: 78,
+    last_exit_info: {
+      code: 78,
+      category: "Sysexits",
+      name: "EX_CONFIG",
+      description: "EX_CONFIG: configuration error",
+    },
     plist_path: "/Users/test/Library/LaunchAgents/com.example.stopped.plist",
     source: "UserAgent",
     status: "Unloaded",
+    status_reason: null,
     last_run_at: null,
     is_home_agent: false,
   },
@@ -47,7 +47,13 @@ type JobRowProps = {
   onRevealInFinder: (job: JobListEntry) => void
 }
 
-function StatusBadge({ status }: { status: JobListEntry["status"] }) {
+function StatusBadge({
+  status,
+  reason,
+}: {
+  status: JobListEntry["status"]
+  reason: string | null
+}) {
   switch (status) {
     case "Running":
       return (
@@ -63,6 +69,12 @@ function StatusBadge({ status }: { status: JobListEntry["status"] }) {
       )
     case "Unloaded":
       return <Badge variant="secondary">Unloaded</Badge>
+    case "CrashLooping":
+      return (
+        <Badge variant="destructive" title={reason ?? undefined}>
+          Crash loop
+        </Badge>
+      )
     default:
       return <Badge variant="outline">Unknown</Badge>
   }
@@ -109,7 +121,7 @@ export function JobRow({
         <SourceBadge source={job.source} />
       </TableCell>
       <TableCell>
-        <StatusBadge status={job.status} />
+        <StatusBadge status={job.status} reason={job.status_reason} />
       </TableCell>
       <TableCell className="text-muted-foreground tabular-nums">
         {job.pid ?? "—"}
@@ -2,6 +2,7 @@ import { describe, it, expect, beforeEach } from "vitest"
 import { renderHook, waitFor, act } from "@testing-library/react"
 import { useJobs } from "./useJobs"
 import { resetFakeHandlers, setFakeHandler } from "@/test-utils/tauri-mock"
+import { emitFakeEvent } from "@/test-utils/tauri-event-mock"
 
 beforeEach(() => {
   resetFakeHandlers()
@@ -75,6 +76,23 @@ describe("useJobs", () => {
     })
   })
 
+  it("reloads when the backend monitor reports a state change", async () => {
+    const { result } = renderHook(() => useJobs())
+
+    await waitFor(() => {
+      expect(result.current.jobs.length).toBe(3)
+    })
+
+    setFakeHandler("list_jobs", () => [])
+    act(() => {
+      emitFakeEvent("job-exited", { label: "com.example.running-agent", pid: 1234, exit_code: 1 })
+    })
+
+    await waitFor(() => {
+      expect(result.current.jobs.length).toBe(0)
+    })
+  })
+
   it("handles error", async () => {
     setFakeHandler("list_jobs", () => {
       throw new Error("Connection failed")
@@ -89,4 +107,25 @@ describe("useJobs", () => {
     expect(result.current.error).toContain("Connection failed")
     expect(result.current.jobs.length).toBe(0)
   })
+
+  it("shows the message of a structured AppError", async () => {
+    setFakeHandler("list_jobs", () => {
+      throw {
+        code: "PermissionDenied",
+        message: "permission denied: list gui/501",
+        operation: "list",
+        target: "gui/501",
+        stderr: "",
+        hint: "",
+      }
+    })
+
+    const { result } = renderHook(() => useJobs())
+
+    await waitFor(() => {
+      expect(result.current.loading).toBe(false)
+    })
+
+    expect(result.current.error).toBe("permission denied: list gui/501")
+  })
 })
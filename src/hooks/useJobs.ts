@@ -1,6 +1,7 @@
 import { useState, useEffect, useCallback } from "react"
+import { listen } from "@tauri-apps/api/event"
 import type { JobListEntry, SourceFilter } from "@/types"
-import { listJobs } from "@/lib/invoke"
+import { formatError, JOB_EVENTS, listJobs } from "@/lib/invoke"
 
 type UseJobsReturn = {
   jobs: JobListEntry[]
@@ -28,7 +29,7 @@ export function useJobs(): UseJobsReturn {
       const result = await listJobs()
       setJobs(result)
     } catch (e) {
-      setError(String(e))
+      setError(formatError(e))
     } finally {
       setLoading(false)
     }
@@ -38,6 +39,21 @@ export function useJobs(): UseJobsReturn {
     refresh()
   }, [refresh])
 
+  // The backend monitor emits an event whenever a job changes state; reload quietly
+  // (without the loading indicator) so the list stays current between manual refreshes.
+  useEffect(() => {
+    const unlisteners = JOB_EVENTS.map((event) =>
+      listen(event, () => {
+        listJobs()
+          .then(setJobs)
+          .catch(() => {})
+      })
+    )
+    return () => {
+      unlisteners.forEach((p) => p.then((unlisten) => unlisten()))
+    }
+  }, [])
+
   const filteredJobs = jobs.filter((job) => {
     const matchesSearch =
       search === "" || job.label.toLowerCase().includes(search.toLowerCase())
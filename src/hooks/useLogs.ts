@@ -1,5 +1,5 @@
 import { useState, useCallback } from "react"
-import { readLogFile } from "@/lib/invoke"
+import { formatError, readLogFile } from "@/lib/invoke"
 
 function stripAnsiAndControl(text: string): string {
   return text
@@ -35,7 +35,7 @@ export function useLogs(): UseLogsReturn {
         result.modified_at ? new Date(Number(result.modified_at)) : null
       )
     } catch (e) {
-      setError(String(e))
+      setError(formatError(e))
       setContent("")
       setModifiedAt(null)
     } finally {
@@ -1,11 +1,87 @@
 import { invoke } from "@tauri-apps/api/core"
 import type { JobListEntry, LaunchdJob, PlistConfig } from "@/types"
 
+// Serialized AppError (src-tauri/src/error.rs). launchctl failures also carry
+// operation, target, stderr and hint.
+export type AppErrorCode =
+  | "ServiceAlreadyLoaded"
+  | "ServiceNotFound"
+  | "ServiceDisabled"
+  | "PermissionDenied"
+  | "InputOutputError"
+  | "InvalidPlist"
+  | "Launchctl"
+  | "Plist"
+  | "Io"
+  | "NotFound"
+
+export type AppErrorPayload = {
+  code: AppErrorCode
+  message: string
+  operation?: string
+  target?: string
+  stderr?: string
+  hint?: string
+}
+
+export function isAppError(e: unknown): e is AppErrorPayload {
+  return (
+    typeof e === "object" &&
+    e !== null &&
+    typeof (e as AppErrorPayload).code === "string" &&
+    typeof (e as AppErrorPayload).message === "string"
+  )
+}
+
+export function formatError(e: unknown): string {
+  return isAppError(e) ? e.message : String(e)
+}
+
 export const listJobs = () => invoke<JobListEntry[]>("list_jobs")
 
 export const getJobDetail = (plistPath: string) =>
   invoke<LaunchdJob>("get_job_detail", { plistPath })
 
+export type RunEventKind = "Started" | "Exited"
+
+export type RunEvent = {
+  label: string
+  kind: RunEventKind
+  pid: number
+  exit_code: number | null
+  at: number
+}
+
+export type HistoryRange = {
+  start: number | null
+  end: number | null
+}
+
+export const getJobHistory = (label: string, range?: HistoryRange) =>
+  invoke<RunEvent[]>("get_job_history", { label, range })
+
+export const setMonitorInterval = (seconds: number) =>
+  invoke<void>("set_monitor_interval", { seconds })
+
+export const getMonitorInterval = () => invoke<number>("get_monitor_interval")
+
+// Events emitted by the background monitor (src-tauri/src/monitor.rs).
+export type JobEventPayloads = {
+  "job-started": { label: string; pid: number }
+  "job-exited": { label: string; pid: number; exit_code: number | null }
+  "job-loaded": { label: string }
+  "job-unloaded": { label: string }
+  "job-crash-loop": { label: string; exits: number; window_secs: number }
+}
+
+export const JOB_EVENTS = [
+  "job-started",
+  "job-exited",
+  "job-loaded",
+  "job-unloaded",
+  "job-crash-loop",
+] as const satisfies readonly (keyof JobEventPayloads)[]
+
 export const startJob = (plistPath: string) =>
   invoke<void>("start_job", { plistPath })
 
@@ -33,20 +109,126 @@ export const createJob = (label: string, config: PlistConfig) =>
 export const deleteJob = (plistPath: string, label: string) =>
   invoke<void>("delete_job", { plistPath, label })
 
+export type LogFormat = "JsonLines" | "Logfmt" | "PlainText"
+
+export type LogLevel = "Trace" | "Debug" | "Info" | "Warning" | "Error" | "Fatal"
+
+export type ParsedLogLine = {
+  timestamp: string | null
+  level: LogLevel | null
+  message: string
+}
+
+export type LogStats = {
+  format: LogFormat
+  error_count: number
+  warning_count: number
+}
+
 export type LogFileResult = {
   content: string
   modified_at: string | null
+  lines: ParsedLogLine[]
+  stats: LogStats
 }
 
 export const readLogFile = (path: string, tailLines?: number) =>
   invoke<LogFileResult>("read_log_file", { path, tailLines })
 
+export const getLogStats = (paths: string[]) =>
+  invoke<Record<string, LogStats>>("get_log_stats", { paths })
+
+export type LogStream = "Stdout" | "Stderr"
+
+export type TimelineLine = {
+  stream: LogStream
+  line_number: number
+  timestamp: string | null
+  text: string
+}
+
+export const readMergedLogs = (
+  stdoutPath: string | null,
+  stderrPath: string | null,
+  tailLines?: number
+) =>
+  invoke<TimelineLine[]>("read_merged_logs", {
+    stdoutPath,
+    stderrPath,
+    tailLines,
+  })
+
+export type UnifiedLogLevel = "Debug" | "Info" | "Default" | "Error" | "Fault"
+
+export type LaunchdEventKind =
+  | "SpawnFailed"
+  | "AbnormalExit"
+  | "Exited"
+  | "Throttled"
+
+export type UnifiedLogEntry = {
+  timestamp: string | null
+  level: UnifiedLogLevel
+  process: string
+  pid: number | null
+  subsystem: string | null
+  category: string | null
+  message: string
+  from_launchd: boolean
+  kind: LaunchdEventKind | null
+}
+
+export const getUnifiedLog = (
+  label: string,
+  pid: number | null,
+  start: number,
+  end?: number
+) => invoke<UnifiedLogEntry[]>("get_unified_log", { label, pid, start, end })
+
 export const clearLogFile = (path: string) =>
   invoke<void>("clear_log_file", { path })
 
 export const openLogInEditor = (path: string) =>
   invoke<void>("open_log_in_editor", { path })
 
+export type Compression = "None" | "Gzip" | "Bzip2" | "Xz"
+
+export type RotationOptions = {
+  owner: string | null
+  mode: string | null
+  count: number
+  size_kb: number | null
+  when: string | null
+  compression: Compression
+}
+
+export type NewsyslogEntry = {
+  log_path: string
+  owner: string | null
+  mode: string
+  count: number
+  size_kb: number | null
+  when: string | null
+  flags: string
+  pid_file: string | null
+  signal: string | null
+  source: string | null
+}
+
+export type LogRotationStatus = {
+  log_path: string
+  covered_by: NewsyslogEntry | null
+}
+
+export const previewLogRotation = (plistPath: string, options: RotationOptions) =>
+  invoke<string>("preview_log_rotation", { plistPath, options })
+
+export const writeLogRotation = (plistPath: string, options: RotationOptions) =>
+  invoke<string>("write_log_rotation", { plistPath, options })
+
+export const getLogRotationStatus = (plistPath: string) =>
+  invoke<LogRotationStatus[]>("get_log_rotation_status", { plistPath })
+
 export const getHomeDir = () => invoke<string>("get_home_dir")
 
 export const revealInFinder = (path: string) =>
@@ -0,0 +1,16 @@
+type EventCallback = (event: { event: string; payload: unknown }) => void
+
+const listeners: Record<string, EventCallback[]> = {}
+
+export async function listen(event: string, handler: EventCallback): Promise<() => void> {
+  listeners[event] = [...(listeners[event] ?? []), handler]
+  return () => {
+    listeners[event] = (listeners[event] ?? []).filter((h) => h !== handler)
+  }
+}
+
+export function emitFakeEvent(event: string, payload: unknown) {
+  for (const handler of listeners[event] ?? []) {
+    handler({ event, payload })
+  }
+}
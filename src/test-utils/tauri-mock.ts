@@ -22,9 +22,11 @@ const fakeJobs: JobListEntry[] = [
     label: "com.example.running-agent",
     pid: 1234,
     last_exit_code: 0,
+    last_exit_info: null,
     plist_path: "/Users/test/Library/LaunchAgents/com.example.running-agent.plist",
     source: "UserAgent",
     status: "Running",
+    status_reason: null,
     last_run_at: String(Date.now()),
     is_home_agent: true,
   },
@@ -32,9 +34,16 @@ const fakeJobs: JobListEntry[] = [
     label: "com.example.stopped-agent",
     pid: null,
     last_exit_code: 78,
+    last_exit_info: {
+      code: 78,
+      category: "Sysexits",
+      name: "EX_CONFIG",
+      description: "EX_CONFIG: configuration error",
+    },
     plist_path: "/Users/test/Library/LaunchAgents/com.example.stopped-agent.plist",
     source: "UserAgent",
     status: "Unloaded",
+    status_reason: null,
     last_run_at: null,
     is_home_agent: false,
   },
@@ -42,9 +51,11 @@ const fakeJobs: JobListEntry[] = [
     label: "com.apple.system-agent",
     pid: 5678,
     last_exit_code: 0,
+    last_exit_info: null,
     plist_path: "/Library/LaunchAgents/com.apple.system-agent.plist",
     source: "SystemAgent",
     status: "Running",
+    status_reason: null,
     last_run_at: String(Date.now() - 3600000),
     is_home_agent: false,
   },
@@ -56,8 +67,10 @@ const fakeJobDetails: Record<string, LaunchdJob> = {
     plist_path: "/Users/test/Library/LaunchAgents/com.example.running-agent.plist",
     source: "UserAgent",
     status: "Running",
+    status_reason: null,
     pid: 1234,
     last_exit_code: 0,
+    last_exit_info: null,
     last_run_at: String(Date.now()),
     plist: {
       ...defaultPlistConfig,
@@ -95,6 +108,11 @@ const handlers: Record<string, CommandHandler> = {
   read_log_file: () => ({
     content: "2024-01-01 12:00:00 INFO Started\n2024-01-01 12:00:01 INFO Running\n",
     modified_at: String(Date.now()),
+    lines: [
+      { timestamp: null, level: "Info", message: "2024-01-01 12:00:00 INFO Started" },
+      { timestamp: null, level: "Info", message: "2024-01-01 12:00:01 INFO Running" },
+    ],
+    stats: { format: "PlainText", error_count: 0, warning_count: 0 },
   }),
   open_log_in_editor: () => undefined,
   reveal_in_finder: () => undefined,
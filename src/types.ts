@@ -1,17 +1,39 @@
 export type JobSource = "UserAgent" | "SystemAgent" | "SystemDaemon"
-export type JobStatus = "Running" | "Loaded" | "Unloaded" | "Unknown"
+export type JobStatus =
+  | "Running"
+  | "Loaded"
+  | "Unloaded"
+  | "Unknown"
+  | "CrashLooping"
 
 // Filter values for the source toolbar. "Home" is a virtual filter (a subset of
 // UserAgent) matching user-authored automations, driven by JobListEntry.is_home_agent.
 export type SourceFilter = JobSource | "All" | "Home"
 
+export type ExitCodeCategory =
+  | "Success"
+  | "Signal"
+  | "Sysexits"
+  | "Shell"
+  | "Launchd"
+  | "Generic"
+
+export type ExitCodeInfo = {
+  code: number
+  category: ExitCodeCategory
+  name: string | null
+  description: string
+}
+
 export type JobListEntry = {
   label: string
   pid: number | null
   last_exit_code: number | null
+  last_exit_info: ExitCodeInfo | null
   plist_path: string
   source: JobSource
   status: JobStatus
+  status_reason: string | null
   last_run_at: string | null
   is_home_agent: boolean
 }
@@ -46,8 +68,10 @@ export type LaunchdJob = {
   plist_path: string
   source: JobSource
   status: JobStatus
+  status_reason: string | null
   pid: number | null
   last_exit_code: number | null
+  last_exit_info: ExitCodeInfo | null
   plist: PlistConfig
   last_run_at: string | null
 }
@@ -16,6 +16,10 @@ export default defineConfig({
         __dirname,
         "./src/test-utils/tauri-mock.ts"
       ),
+      "@tauri-apps/api/event": path.resolve(
+        __dirname,
+        "./src/test-utils/tauri-event-mock.ts"
+      ),
     },
   },
 })